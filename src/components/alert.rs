@@ -1,4 +1,5 @@
 use super::*;
+use crate::util::*;
 use gloo_timers::callback::Timeout;
 use yew::prelude::*;
 
@@ -27,7 +28,15 @@ impl AlertDuration {
 #[derive(Properties, PartialEq, Clone)]
 pub struct AlertProps {
     /// The alert open state.
-    pub state: UseStateHandle<bool>,
+    #[prop_or_default]
+    pub state: Option<UseStateHandle<bool>>,
+    /// Whether the alert is open. Used along with `on_change` in place of
+    /// `state`.
+    #[prop_or_default]
+    pub value: Option<bool>,
+    /// The callback called when the alert open state changes.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_change: Callback<bool>,
     /// The alert title.
     #[prop_or_default]
     pub title: String,
@@ -47,12 +56,16 @@ pub struct AlertProps {
 pub fn Alert(props: &AlertProps) -> Html {
     let AlertProps {
         state,
+        value,
+        on_change,
         title,
         duration,
         on_close,
         children,
     } = props.clone();
 
+    let state = use_value_handle(state, value, on_change);
+
     let timeout_close_callback = on_close.clone();
     let timeout_close_state = state.clone();
     let timeout_state = use_state(|| None);
@@ -1,3 +1,4 @@
+use super::*;
 use crate::util::*;
 use yew::prelude::*;
 
@@ -5,10 +6,29 @@ use yew::prelude::*;
 #[derive(Properties, PartialEq, Clone)]
 pub struct CheckboxProps {
     /// The checkbox state.
-    pub state: UseStateHandle<bool>,
+    #[prop_or_default]
+    pub state: Option<UseStateHandle<bool>>,
+    /// The checkbox value. Used along with `on_change` in place of `state`.
+    #[prop_or_default]
+    pub value: Option<bool>,
+    /// The callback called when the checkbox value changes.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_change: Callback<bool>,
+    /// The callback called when the checkbox loses focus.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_blur: Callback<()>,
+    /// The name of the field within a surrounding form.
+    #[prop_or_default]
+    pub name: String,
     /// The checkbox label.
     #[prop_or_default]
     pub label: String,
+    /// Help text describing the checkbox.
+    #[prop_or_default]
+    pub help: String,
+    /// An optional error message.
+    #[prop_or_default]
+    pub error: Option<String>,
     /// Whether the checkbox is disabled.
     #[prop_or(false)]
     pub disabled: bool,
@@ -19,24 +39,56 @@ pub struct CheckboxProps {
 pub fn Checkbox(props: &CheckboxProps) -> Html {
     let CheckboxProps {
         state,
+        value,
+        on_change,
+        on_blur,
+        name,
         label,
+        help,
+        error,
         disabled,
     } = props.clone();
 
+    let state = use_value_handle(state, value, on_change);
+
     let checked = *state;
+    let input_node = use_node_ref();
+    let form_error = use_form_registration(
+        &name,
+        FormField {
+            label: label.clone(),
+            node: input_node.clone(),
+            ..FormField::new(FormValue::Bool(checked), false, error.clone())
+        },
+    );
+    use_form_restore(&name, {
+        let state = state.clone();
+        move |value| {
+            if let FormValue::Bool(value) = value {
+                state.set(value);
+            }
+        }
+    });
     let onclick = move |event: MouseEvent| {
         let new_value = checkbox_checked(event);
         state.set(new_value);
     };
+    let onblur = move |_| {
+        on_blur.emit(());
+    };
+    let error = error.or(form_error);
 
     html! {
-        <div class="base-checkbox-container">
+        <FieldWrapper {help} {error} {disabled} class="base-checkbox-container">
             <label class={classes!("base-checkbox", disabled.then_some("base-checkbox-disabled"))}>
                 <span class="base-checkbox-label">{label}</span>
                 <input
+                    ref={input_node}
                     type="checkbox"
+                    {name}
                     {checked}
                     {onclick}
+                    {onblur}
                     {disabled}
                     class="base-checkbox-input"
                 />
@@ -44,6 +96,6 @@ pub fn Checkbox(props: &CheckboxProps) -> Html {
                     <img src="assets/svg/check-solid.svg" class="base-checkmark-icon" />
                 </span>
             </label>
-        </div>
+        </FieldWrapper>
     }
 }
@@ -0,0 +1,390 @@
+use crate::util::*;
+use std::ops::Range;
+use std::rc::Rc;
+
+/// A match between a chip option and the text the user has typed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChipMatch {
+    /// A score indicating the strength of the match. Lower scores indicate
+    /// stronger matches.
+    pub score: usize,
+    /// The ranges of characters within the option that matched the typed
+    /// text, in order. Ranges are measured in characters rather than bytes.
+    pub ranges: Vec<Range<usize>>,
+}
+
+impl ChipMatch {
+    /// Creates a new chip match.
+    pub fn new(score: usize, ranges: Vec<Range<usize>>) -> Self {
+        Self { score, ranges }
+    }
+
+    /// Creates a new chip match covering a single range of characters.
+    pub fn contiguous(score: usize, range: Range<usize>) -> Self {
+        Self::new(score, vec![range])
+    }
+
+    /// Gets the indices of all matched characters.
+    pub fn indices(&self) -> Vec<usize> {
+        self.ranges.iter().flat_map(Clone::clone).collect()
+    }
+}
+
+/// A strategy for matching chip options against the text the user has typed.
+pub trait ChipMatcher {
+    /// Compares an option to the typed text, returning the match, or `None`
+    /// if the option does not match. Empty text matches no option.
+    fn matches(&self, option: &str, query: &str) -> Option<ChipMatch>;
+}
+
+/// Lowercases a string, returning the lowercase characters along with the
+/// index of the original character each one came from.
+fn fold(value: &str) -> (Vec<char>, Vec<usize>) {
+    fold_case(value)
+        .into_iter()
+        .map(|(index, c)| (c, index))
+        .unzip()
+}
+
+/// Maps a non-empty range of lowercase characters back to the range of
+/// original characters they came from.
+fn original_range(origins: &[usize], range: Range<usize>) -> Range<usize> {
+    origins[range.start]..origins[range.end - 1] + 1
+}
+
+/// Groups sorted character indices into contiguous ranges.
+fn index_ranges(indices: &[usize]) -> Vec<Range<usize>> {
+    let mut ranges = Vec::<Range<usize>>::new();
+
+    for index in indices {
+        match ranges.last_mut() {
+            Some(range) if range.end == *index => range.end += 1,
+            _ => ranges.push(*index..*index + 1),
+        }
+    }
+
+    ranges
+}
+
+/// Matches options starting with the typed text. Shorter options rank
+/// higher.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PrefixMatcher;
+
+impl ChipMatcher for PrefixMatcher {
+    fn matches(&self, option: &str, query: &str) -> Option<ChipMatch> {
+        if query.is_empty() {
+            return None;
+        }
+
+        let (option, origins) = fold(option);
+        let (query, _) = fold(query);
+
+        option.starts_with(&query).then(|| {
+            ChipMatch::contiguous(
+                option.len() - query.len(),
+                original_range(&origins, 0..query.len()),
+            )
+        })
+    }
+}
+
+/// Matches options containing the typed text anywhere. Options containing
+/// the text closer to their start rank higher.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SubstringMatcher;
+
+impl ChipMatcher for SubstringMatcher {
+    fn matches(&self, option: &str, query: &str) -> Option<ChipMatch> {
+        if query.is_empty() {
+            return None;
+        }
+
+        let (option, origins) = fold(option);
+        let (query, _) = fold(query);
+
+        option
+            .windows(query.len())
+            .position(|window| window == query.as_slice())
+            .map(|start| {
+                ChipMatch::contiguous(
+                    origins[start],
+                    original_range(&origins, start..start + query.len()),
+                )
+            })
+    }
+}
+
+/// Matches options containing a word starting with the typed text. Words are
+/// separated by any character that is not alphanumeric. Options whose
+/// earlier words match rank higher.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct WordStartMatcher;
+
+impl ChipMatcher for WordStartMatcher {
+    fn matches(&self, option: &str, query: &str) -> Option<ChipMatch> {
+        if query.is_empty() {
+            return None;
+        }
+
+        let (option, origins) = fold(option);
+        let (query, _) = fold(query);
+
+        (0..option.len())
+            .filter(|index| {
+                option[*index].is_alphanumeric()
+                    && (*index == 0 || !option[*index - 1].is_alphanumeric())
+            })
+            .enumerate()
+            .find(|(_, start)| option[*start..].starts_with(&query))
+            .map(|(word, start)| {
+                ChipMatch::contiguous(word, original_range(&origins, start..start + query.len()))
+            })
+    }
+}
+
+/// Matches options containing the characters of the typed text in order,
+/// though not necessarily adjacent to one another. Options with fewer
+/// characters between the matched characters rank higher.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SubsequenceMatcher;
+
+impl ChipMatcher for SubsequenceMatcher {
+    fn matches(&self, option: &str, query: &str) -> Option<ChipMatch> {
+        if query.is_empty() {
+            return None;
+        }
+
+        option_match_indices(option, query)
+            .map(|(score, indices)| ChipMatch::new(score, index_ranges(&indices)))
+    }
+}
+
+/// Matches options containing text within a maximum number of single
+/// character edits of the typed text, tolerating typos. Options needing fewer
+/// edits rank higher.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TypoTolerantMatcher {
+    /// The maximum number of insertions, deletions or substitutions.
+    pub max_distance: usize,
+}
+
+impl TypoTolerantMatcher {
+    /// Creates a new typo-tolerant matcher.
+    pub fn new(max_distance: usize) -> Self {
+        Self { max_distance }
+    }
+}
+
+impl Default for TypoTolerantMatcher {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl ChipMatcher for TypoTolerantMatcher {
+    fn matches(&self, option: &str, query: &str) -> Option<ChipMatch> {
+        if query.is_empty() {
+            return None;
+        }
+
+        let (option, origins) = fold(option);
+        let (query, _) = fold(query);
+        let width = option.len() + 1;
+
+        // The edit distance between the first `row` characters of the query
+        // and some substring of the option ending at `column`, along with the
+        // start of that substring.
+        let mut distances = vec![(0usize, 0usize); (query.len() + 1) * width];
+
+        for (column, distance) in distances.iter_mut().take(width).enumerate() {
+            *distance = (0, column);
+        }
+
+        for row in 1..=query.len() {
+            distances[row * width] = (row, 0);
+
+            for column in 1..width {
+                let cost = usize::from(query[row - 1] != option[column - 1]);
+                let (substitute, substitute_start) = distances[(row - 1) * width + column - 1];
+                let (delete, delete_start) = distances[(row - 1) * width + column];
+                let (insert, insert_start) = distances[row * width + column - 1];
+
+                distances[row * width + column] = [
+                    (substitute + cost, substitute_start),
+                    (delete + 1, delete_start),
+                    (insert + 1, insert_start),
+                ]
+                .into_iter()
+                .min_by_key(|(distance, _)| *distance)
+                .unwrap_or_default();
+            }
+        }
+
+        let last_row = query.len() * width;
+        let (end, (distance, start)) = (1..width)
+            .map(|column| (column, distances[last_row + column]))
+            .min_by_key(|(_, (distance, _))| *distance)?;
+
+        // A query no longer than the distance would match any option.
+        (distance <= self.max_distance && distance < query.len() && start < end).then(|| {
+            ChipMatch::contiguous(
+                distance * width + origins[start],
+                original_range(&origins, start..end),
+            )
+        })
+    }
+}
+
+/// A shared chip matching strategy, for use as a component property.
+pub struct SharedChipMatcher(Rc<dyn ChipMatcher>);
+
+impl SharedChipMatcher {
+    /// Creates a new shared chip matcher.
+    pub fn new(matcher: impl ChipMatcher + 'static) -> Self {
+        Self(Rc::new(matcher))
+    }
+
+    /// Compares an option to the typed text.
+    pub fn matches(&self, option: &str, query: &str) -> Option<ChipMatch> {
+        self.0.matches(option, query)
+    }
+}
+
+impl Default for SharedChipMatcher {
+    fn default() -> Self {
+        Self::new(SubsequenceMatcher)
+    }
+}
+
+impl Clone for SharedChipMatcher {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl PartialEq for SharedChipMatcher {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matchers_ignore_case() {
+        assert_eq!(
+            PrefixMatcher.matches("Apple", "AP"),
+            Some(ChipMatch::contiguous(3, 0..2))
+        );
+        assert_eq!(
+            SubstringMatcher.matches("Pineapple", "APP"),
+            Some(ChipMatch::contiguous(4, 4..7))
+        );
+        assert_eq!(
+            WordStartMatcher.matches("green apple", "APP"),
+            Some(ChipMatch::contiguous(1, 6..9))
+        );
+        assert_eq!(
+            TypoTolerantMatcher::default().matches("Apple", "APLE"),
+            Some(ChipMatch::contiguous(7, 1..5))
+        );
+    }
+
+    #[test]
+    fn matchers_map_ranges_to_original_characters() {
+        // 'İ' lowercases to two characters, which both map back to it.
+        assert_eq!(
+            PrefixMatcher.matches("İzmir", "i"),
+            Some(ChipMatch::contiguous(5, 0..1))
+        );
+        assert_eq!(
+            SubstringMatcher.matches("İzmir", "zm"),
+            Some(ChipMatch::contiguous(1, 1..3))
+        );
+        assert_eq!(
+            WordStartMatcher.matches("Old İzmir", "i\u{307}z"),
+            Some(ChipMatch::contiguous(1, 4..6))
+        );
+        assert_eq!(
+            SubsequenceMatcher.matches("İzmir", "ir"),
+            Some(ChipMatch::new(6, vec![0..1, 4..5]))
+        );
+    }
+
+    /// Gets the options matching the query, from the strongest match to the
+    /// weakest.
+    fn rank<'a>(matcher: &impl ChipMatcher, options: &[&'a str], query: &str) -> Vec<&'a str> {
+        let mut matches = options
+            .iter()
+            .filter_map(|option| matcher.matches(option, query).map(|m| (*option, m.score)))
+            .collect::<Vec<_>>();
+        matches.sort_by_key(|(_, score)| *score);
+        matches.into_iter().map(|(option, _)| option).collect()
+    }
+
+    #[test]
+    fn matchers_reject_empty_query() {
+        assert_eq!(PrefixMatcher.matches("Apple", ""), None);
+        assert_eq!(SubstringMatcher.matches("Apple", ""), None);
+        assert_eq!(WordStartMatcher.matches("Apple", ""), None);
+        assert_eq!(SubsequenceMatcher.matches("Apple", ""), None);
+        assert_eq!(TypoTolerantMatcher::default().matches("Apple", ""), None);
+        assert_eq!(PrefixMatcher.matches("", ""), None);
+        assert_eq!(TypoTolerantMatcher::default().matches("", ""), None);
+    }
+
+    #[test]
+    fn prefix_matcher_ranks_shorter_options_higher() {
+        assert_eq!(
+            rank(&PrefixMatcher, &["apricot", "Apple", "banana", "ape"], "ap"),
+            vec!["ape", "Apple", "apricot"]
+        );
+    }
+
+    #[test]
+    fn substring_matcher_ranks_earlier_matches_higher() {
+        assert_eq!(
+            rank(
+                &SubstringMatcher,
+                &["banana", "orange", "plum", "ant", "mango"],
+                "an"
+            ),
+            vec!["ant", "banana", "mango", "orange"]
+        );
+    }
+
+    #[test]
+    fn word_start_matcher_ranks_earlier_words_higher() {
+        assert_eq!(
+            rank(
+                &WordStartMatcher,
+                &["big red apple", "grape", "green apple", "apple pie"],
+                "ap"
+            ),
+            vec!["apple pie", "green apple", "big red apple"]
+        );
+    }
+
+    #[test]
+    fn subsequence_matcher_ranks_closer_characters_higher() {
+        assert_eq!(
+            rank(&SubsequenceMatcher, &["cart", "dog", "cat", "ct"], "ct"),
+            vec!["ct", "cat", "cart"]
+        );
+    }
+
+    #[test]
+    fn typo_tolerant_matcher_ranks_fewer_edits_higher() {
+        assert_eq!(
+            rank(
+                &TypoTolerantMatcher::default(),
+                &["apple", "banana", "staple", "maple"],
+                "aple"
+            ),
+            vec!["maple", "staple", "apple"]
+        );
+    }
+}
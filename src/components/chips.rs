@@ -1,100 +1,216 @@
 use super::*;
 use crate::util::*;
+use web_sys::HtmlInputElement;
 use yew::prelude::*;
 use yew_hooks::use_click_away;
 
-/// Compares an option to a typed out value, returning a score indicating the
-/// strength of the match, or `None` if the strings do not match.
-fn option_match(option: &str, value: &str) -> Option<usize> {
-    let option = option.to_lowercase();
-    let value = value.to_lowercase();
-    let mut score = 0;
-    let mut indices_since_last_match = 0;
-    let option_chars = option.chars();
-    let mut value_chars = value.chars().peekable();
-    let mut any_match = false;
-
-    if option == value {
-        return Some(0);
-    }
-
-    for option_char in option_chars {
-        indices_since_last_match += 1;
-
-        match value_chars.peek() {
-            Some(value_char) => {
-                if option_char == *value_char {
-                    score += indices_since_last_match;
-                    indices_since_last_match = 0;
-                    value_chars.next();
-                    any_match = true;
-                }
-            }
-            None => break,
-        }
+/// An item that can be chosen as a chip.
+pub trait ChipItem: SelectItem {
+    /// Creates an item from text entered by the user, for free-form entry and
+    /// for options created from the typed text. By default items cannot be
+    /// created from text, so only the available options can be chosen.
+    fn from_text(_text: &str) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        None
     }
+}
 
-    if any_match && value_chars.next().is_none() {
-        Some(score)
-    } else {
-        None
+impl ChipItem for String {
+    fn from_text(text: &str) -> Option<Self> {
+        Some(text.to_owned())
     }
 }
 
-/// Limits the number of options.
-fn limit_options<T: Clone>(options: &[T], limit: usize) -> Vec<T> {
-    let limit_index = if options.len() > limit {
-        limit
-    } else {
-        options.len()
-    };
+impl ChipItem for &'static str {}
 
-    (options[..limit_index]).to_owned()
-}
+impl<T: FormEnum> ChipItem for T {}
 
 /// Returns a list of possible options, taking into account the complete list
 /// of options, the currently selected options, and the option the user has
-/// begun to type out.
-fn get_possible_options(
-    all_options: &[String],
-    selected_options: &[String],
+/// begun to type out. Each option is paired with the indices of its
+/// characters matching the typed text. Unless `filter` is set, options that
+/// do not match are kept, e.g. when they have already been filtered by an
+/// option source.
+fn get_possible_options<T: SelectItem + PartialEq + Clone>(
+    all_options: &[T],
+    selected_options: &[T],
     next_option: &str,
+    matcher: &SharedChipMatcher,
+    filter: bool,
     limit: usize,
-) -> Vec<String> {
+) -> Vec<(T, Vec<usize>)> {
     let unselected_options = all_options
         .iter()
         .filter_map(|option| (!selected_options.contains(option)).then_some(option.to_owned()))
         .collect::<Vec<_>>();
 
     if next_option.is_empty() {
-        return limit_options(&unselected_options, limit);
+        let options = unselected_options
+            .into_iter()
+            .map(|option| (option, Vec::new()))
+            .collect::<Vec<_>>();
+        return limit_options(&options, limit);
+    }
+
+    if !filter {
+        let options = unselected_options
+            .into_iter()
+            .map(|option| {
+                let indices = matcher
+                    .matches(&option.label(), next_option)
+                    .map(|chip_match| chip_match.indices())
+                    .unwrap_or_default();
+                (option, indices)
+            })
+            .collect::<Vec<_>>();
+        return limit_options(&options, limit);
     }
 
     let mut matches = unselected_options
         .into_iter()
-        .filter_map(|option| option_match(&option, next_option).map(|score| (option, score)))
+        .filter_map(|option| {
+            matcher
+                .matches(&option.label(), next_option)
+                .map(|chip_match| (option, chip_match))
+        })
         .collect::<Vec<_>>();
 
-    matches.sort_by(|(_, score1), (_, score2)| score1.cmp(score2));
+    matches.sort_by_key(|(_, chip_match)| chip_match.score);
 
     let limited_matches = limit_options(&matches, limit);
 
     limited_matches
         .into_iter()
-        .map(|(option, _)| option)
+        .map(|(option, chip_match)| (option, chip_match.indices()))
         .collect()
 }
 
+/// Splits text typed or pasted into a free-form chips input into the chips it
+/// contains, along with any remaining text not yet completed by a delimiter.
+/// Pasted text is also split on line breaks, and its final segment is treated
+/// as complete.
+fn split_chips(text: &str, delimiters: &[char], pasted: bool) -> (Vec<String>, String) {
+    let mut segments = text
+        .split(|c: char| delimiters.contains(&c) || (pasted && (c == '\n' || c == '\r')))
+        .map(str::to_owned)
+        .collect::<Vec<_>>();
+    let rest = if pasted {
+        String::new()
+    } else {
+        segments.pop().unwrap_or_default()
+    };
+
+    (segments, rest)
+}
+
+/// Appends chips created from text to the existing chips. Text is trimmed,
+/// and blank or duplicate chips, along with chips that cannot be created from
+/// text, are dropped. No chips are appended beyond `max_chips`.
+fn append_chips<T: ChipItem + PartialEq + Clone>(
+    chips: &[T],
+    values: Vec<String>,
+    max_chips: Option<usize>,
+) -> Vec<T> {
+    let mut chips = chips.to_vec();
+
+    for value in values {
+        let value = value.trim();
+
+        if max_chips.is_some_and(|max_chips| chips.len() >= max_chips) {
+            break;
+        }
+
+        if let Some(chip) = (!value.is_empty()).then(|| T::from_text(value)).flatten() {
+            if !chips.contains(&chip) {
+                chips.push(chip);
+            }
+        }
+    }
+
+    chips
+}
+
+/// The options suggested in a chips input's dropdown, along with the listbox
+/// entries for them.
+struct ChipSuggestions<T> {
+    /// The suggested options, each paired with the indices of its characters
+    /// matching the typed text.
+    options: Vec<(T, Vec<usize>)>,
+    /// The typed text that can be created as a new option, if any.
+    create_value: Option<String>,
+    /// The listbox entry for each suggested option, followed by the entry
+    /// offering to create a new option.
+    entries: Vec<ListboxEntry>,
+}
+
+/// Gets the ID of the element of the chip at the given index.
+fn chip_id(id: &str, index: usize) -> String {
+    format!("{id}-chip-{index}")
+}
+
+/// Moves the chip at one index to another, shifting the chips in between.
+fn move_chip<T: Clone>(state: &ValueHandle<Vec<T>>, from: usize, to: usize) {
+    if from != to && from < state.len() && to < state.len() {
+        let mut chips = (**state).clone();
+        let chip = chips.remove(from);
+        chips.insert(to, chip);
+        state.set(chips);
+    }
+}
+
 /// Chips properties.
 #[derive(Properties, PartialEq, Clone)]
-pub struct ChipsProps {
+pub struct ChipsProps<T: ChipItem + PartialEq + Clone + 'static> {
     /// The state of the currently selected chips.
-    pub state: UseStateHandle<Vec<String>>,
+    #[prop_or_default]
+    pub state: Option<UseStateHandle<Vec<T>>>,
+    /// The currently selected chips. Used along with `on_change` in place of
+    /// `state`.
+    #[prop_or_default]
+    pub value: Option<Vec<T>>,
+    /// The callback called when the selected chips changes.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_change: Callback<Vec<T>>,
+    /// The callback called when the chips input loses focus.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_blur: Callback<()>,
+    /// The name of the field within a surrounding form.
+    #[prop_or_default]
+    pub name: String,
     /// The list of chip options.
-    pub options: Vec<String>,
+    #[prop_or_default]
+    pub options: Vec<T>,
+    /// A source to load chip options from in place of `options`. The text
+    /// typed into the chips input is passed to the source as the query.
+    #[prop_or_default]
+    pub source: Option<SharedOptionSource<T>>,
+    /// A callback rendering the body of a chip. Chip labels are displayed if
+    /// this is not provided.
+    #[prop_or_default]
+    pub render_chip: Option<Callback<T, Html>>,
+    /// A callback rendering an option in the dropdown. Option labels, with
+    /// the characters matching the typed text highlighted, are displayed if
+    /// this is not provided.
+    #[prop_or_default]
+    pub render_option: Option<Callback<T, Html>>,
+    /// A callback choosing the style of each chip. Chips are unstyled if this
+    /// is not provided.
+    #[prop_or_default]
+    pub chip_style: Option<Callback<T, BadgeStyle>>,
+    /// The time in milliseconds to wait after the chips input changes before
+    /// loading options from the source.
+    #[prop_or(300)]
+    pub debounce_ms: u32,
     /// The maximum number of options to display in the dropdown.
     #[prop_or(10)]
     pub option_limit: usize,
+    /// The heights of the rows in the dropdown. Only the rows scrolled into
+    /// view are rendered if this is set, allowing `option_limit` to be raised
+    /// without slowing down the dropdown.
+    #[prop_or_default]
+    pub row_height: Option<RowHeight>,
     /// The chips input label.
     #[prop_or_default]
     pub label: String,
@@ -104,39 +220,305 @@ pub struct ChipsProps {
     /// The maximum number of characters allowed in the chip input.
     #[prop_or(524288)]
     pub max_length: usize,
+    /// Whether at least one chip is required.
+    #[prop_or(false)]
+    pub required: bool,
+    /// Whether to mark the chips input as optional when it is not required.
+    #[prop_or(false)]
+    pub show_optional: bool,
+    /// Help text describing the chips input.
+    #[prop_or_default]
+    pub help: String,
     /// An optional error message.
     #[prop_or_default]
     pub error: Option<String>,
     /// Whether the chip input is disabled.
     #[prop_or(false)]
     pub disabled: bool,
+    /// Whether every chip can be removed at once with an inline clear button
+    /// or the Escape key.
+    #[prop_or(false)]
+    pub clearable: bool,
+    /// The callback called when the chips input is cleared.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_clear: Callback<()>,
+    /// The minimum number of chips. An error is reported while there are
+    /// fewer chips, unless there are none and chips are not required.
+    #[prop_or(0)]
+    pub min_chips: usize,
+    /// The maximum number of chips. The chips input is disabled once it is
+    /// reached.
+    #[prop_or_default]
+    pub max_chips: Option<usize>,
+    /// Whether chips can be reordered, by dragging them or by pressing
+    /// Alt+ArrowLeft or Alt+ArrowRight while a chip is focused.
+    #[prop_or(false)]
+    pub reorderable: bool,
+    /// Whether chips can be edited as text, by double-clicking them or by
+    /// pressing Enter or F2 while a chip is focused. Only chips that can be
+    /// created from text can be edited.
+    #[prop_or(false)]
+    pub editable: bool,
+    /// Whether any text can be entered as a chip, rather than only the
+    /// available options. Chips are added by pressing Enter or Tab, or by
+    /// typing one of the delimiters.
+    #[prop_or(false)]
+    pub free_form: bool,
+    /// The characters separating chips in free-form mode, both when typing
+    /// and when pasting text.
+    #[prop_or(vec![',', ';'])]
+    pub delimiters: Vec<char>,
+    /// The strategy used to match options against the typed text.
+    #[prop_or_default]
+    pub matcher: SharedChipMatcher,
+    /// Validators applied to the label of each chip. Chips failing validation
+    /// are still added, but are marked as invalid.
+    #[prop_or_default]
+    pub chip_validators: Validators<str>,
+    /// The callback called with the typed text when the user chooses to
+    /// create a new option from it. A row offering to create the option is
+    /// displayed when the text matches no option or chip exactly, and Enter
+    /// creates it when nothing else matches. The typed text is cleared, and
+    /// the callback is responsible for adding the option, which may happen
+    /// asynchronously, and adding it as a chip.
+    #[prop_or_default]
+    pub on_create: Option<Callback<String>>,
 }
 
 /// A chip selection component.
 #[function_component]
-pub fn Chips(props: &ChipsProps) -> Html {
+pub fn Chips<T: ChipItem + PartialEq + Clone + 'static = String>(props: &ChipsProps<T>) -> Html {
     let ChipsProps {
         state,
+        value,
+        on_change,
+        on_blur,
+        name,
         options,
+        source,
+        render_chip,
+        render_option,
+        chip_style,
+        debounce_ms,
         option_limit,
+        row_height,
         label,
         placeholder,
         max_length,
+        required,
+        show_optional,
+        help,
         error,
         disabled,
+        clearable,
+        on_clear,
+        min_chips,
+        max_chips,
+        reorderable,
+        editable,
+        on_create,
+        free_form,
+        delimiters,
+        chip_validators,
+        matcher,
     } = props.clone();
 
+    let state = use_value_handle(state, value, on_change);
+
+    let chip_labels = state.iter().map(SelectItem::label).collect::<Vec<_>>();
+    let chip_errors = chip_labels
+        .iter()
+        .map(|chip| chip_validators.validate(chip))
+        .collect::<Vec<_>>();
+    let chip_error = chip_labels
+        .iter()
+        .zip(chip_errors.iter())
+        .find_map(|(chip, error)| error.as_ref().map(|error| format!("{chip}: {error}")));
+    let count_error = (state.len() < min_chips && (required || !state.is_empty())).then(|| {
+        format!(
+            "Must have at least {min_chips} chip{}",
+            if min_chips == 1 { "" } else { "s" }
+        )
+    });
+    let chip_error = count_error.or(chip_error);
+    let at_max = max_chips.is_some_and(|max_chips| state.len() >= max_chips);
+    let input_node = use_node_ref();
+    let form_error = use_form_registration(
+        &name,
+        FormField {
+            label: label.clone(),
+            node: input_node.clone(),
+            ..FormField::new(
+                FormValue::List(chip_labels.clone()),
+                required,
+                error.clone().or_else(|| chip_error.clone()),
+            )
+        },
+    );
+    // Restored chips are matched to the options by label, falling back to
+    // creating them from their labels.
+    use_form_restore(&name, {
+        let state = state.clone();
+        let options = options.clone();
+        move |value| {
+            if let FormValue::List(value) = value {
+                let chips = value
+                    .iter()
+                    .filter_map(|label| {
+                        options
+                            .iter()
+                            .find(|option| option.label() == *label)
+                            .cloned()
+                            .or_else(|| T::from_text(label))
+                    })
+                    .collect();
+                state.set(chips);
+            }
+        }
+    });
+    let error = error.or(chip_error).or(form_error);
     let next_chip_state = use_state(String::new);
     let next_chip = (*next_chip_state).clone();
     let id_state = use_state(new_id);
     let id = (*id_state).clone();
     let dropdown_open = use_state(|| false);
-    let possible_options = get_possible_options(&options, &state, &next_chip_state, option_limit);
+    let remote = use_option_source(source, next_chip.clone(), *dropdown_open, debounce_ms);
+    // Matching every option against the typed text is costly for long lists,
+    // so the suggestions are only rebuilt when the options, the chips or the
+    // typed text change.
+    let suggestions = use_memo(
+        |(options, remote_options, chips, next_chip, matcher, option_limit, creatable)| {
+            // Options loaded from a source are already filtered by the
+            // source, and are limited by pagination rather than by
+            // `option_limit`.
+            let (known_options, possible_options) = match remote_options {
+                Some(remote_options) => (
+                    remote_options,
+                    get_possible_options(
+                        remote_options,
+                        chips,
+                        next_chip,
+                        matcher,
+                        false,
+                        usize::MAX,
+                    ),
+                ),
+                None => (
+                    options,
+                    get_possible_options(options, chips, next_chip, matcher, true, *option_limit),
+                ),
+            };
+            let create_value = creatable
+                .then(|| creatable_value(known_options.iter().chain(chips.iter()), next_chip))
+                .flatten();
+            // The row offering to create an option follows the matching
+            // options.
+            let entries = possible_options
+                .iter()
+                .map(|(option, _)| ListboxEntry::new(option.label(), option.disabled()))
+                .chain(
+                    create_value
+                        .iter()
+                        .map(|value| ListboxEntry::new(value.clone(), false)),
+                )
+                .collect();
+
+            ChipSuggestions {
+                options: possible_options,
+                create_value,
+                entries,
+            }
+        },
+        (
+            options.clone(),
+            remote.active().then(|| remote.options()),
+            (*state).clone(),
+            next_chip.clone(),
+            matcher.clone(),
+            option_limit,
+            on_create.is_some(),
+        ),
+    );
+    let possible_options = &suggestions.options;
+    let create_value = &suggestions.create_value;
+    let show_empty = remote.active()
+        && !next_chip.is_empty()
+        && possible_options.is_empty()
+        && create_value.is_none();
+    let show_status = remote.active() && (remote.loading() || remote.error().is_some());
+    let open = *dropdown_open
+        && !at_max
+        && (!possible_options.is_empty() || create_value.is_some() || show_status || show_empty);
+    let listbox = use_listbox();
+    let entries = &suggestions.entries;
+    let highlighted = listbox.highlighted().filter(|index| *index < entries.len());
+    let first_enabled = next_enabled(entries, None, true);
+    let choose = {
+        let state = state.clone();
+        let next_chip_state = next_chip_state.clone();
+        let listbox = listbox.clone();
+        let suggestions = suggestions.clone();
+        move |index: usize| {
+            let ChipSuggestions {
+                options: possible_options,
+                create_value,
+                ..
+            } = &*suggestions;
+            if at_max {
+                return;
+            }
+
+            if let Some((option, _)) = possible_options.get(index) {
+                if option.disabled() {
+                    return;
+                }
+
+                let mut chips = (*state).clone();
+                chips.push(option.clone());
+                state.set(chips);
+            } else if let Some(value) = create_value {
+                if let Some(on_create) = &on_create {
+                    on_create.emit(value.clone());
+                }
+            } else {
+                return;
+            }
+
+            next_chip_state.set(String::new());
+            listbox.highlight(None);
+        }
+    };
+    let add_chips = {
+        let state = state.clone();
+        let next_chip_state = next_chip_state.clone();
+        move |values: Vec<String>, rest: String| {
+            let chips = append_chips(&state, values, max_chips);
+
+            if chips != *state {
+                state.set(chips);
+            }
+
+            next_chip_state.set(rest);
+        }
+    };
     let oninput = {
         let oninput_next_chip_state = next_chip_state.clone();
+        let add_chips = add_chips.clone();
+        let delimiters = delimiters.clone();
+        let dropdown_open = dropdown_open.clone();
+        let listbox = listbox.clone();
         move |event: InputEvent| {
+            dropdown_open.set(true);
+            listbox.highlight(None);
+            let pasted = event.input_type() == "insertFromPaste";
             let new_next_chip = input_event_value(event);
-            oninput_next_chip_state.set(new_next_chip);
+
+            if free_form && (pasted || new_next_chip.contains(&delimiters[..])) {
+                let (values, rest) = split_chips(&new_next_chip, &delimiters, pasted);
+                add_chips(values, rest);
+            } else {
+                oninput_next_chip_state.set(new_next_chip);
+            }
         }
     };
     let onfocusin = {
@@ -145,50 +527,379 @@ pub fn Chips(props: &ChipsProps) -> Html {
             dropdown_open_focusin.set(true);
         }
     };
+    let can_clear = clearable && !(state.is_empty() && next_chip.is_empty());
+    let clear = {
+        let state = state.clone();
+        let next_chip_state = next_chip_state.clone();
+        let input_node = input_node.clone();
+        Callback::from(move |_| {
+            state.set(Vec::new());
+            next_chip_state.set(String::new());
+            focus_element(&input_node);
+            on_clear.emit(());
+        })
+    };
     let onkeydown = {
-        let first_option = possible_options.first().map(|option| option.to_owned());
-        let onkeydown_current_chips = state.clone();
-        let onkeydown_next_chip = next_chip_state.clone();
-        move |event: KeyboardEvent| match event.key_code() {
-            13 => {
-                // enter
-                if let Some(ref option) = first_option {
-                    let mut chips = (*onkeydown_current_chips).clone();
-                    chips.push(option.to_owned());
-                    onkeydown_current_chips.set(chips);
-                    onkeydown_next_chip.set(String::new());
+        let state = state.clone();
+        let next_chip_state = next_chip_state.clone();
+        let dropdown_open = dropdown_open.clone();
+        let listbox = listbox.clone();
+        let input_node = input_node.clone();
+        let id = id.clone();
+        let clear = clear.clone();
+        let choose = choose.clone();
+        let suggestions = suggestions.clone();
+        move |event: KeyboardEvent| {
+            let next_chip = (*next_chip_state).clone();
+            let key = event.key();
+
+            match key.as_str() {
+                "ArrowDown" | "ArrowUp" if !open => {
+                    event.prevent_default();
+                    dropdown_open.set(true);
                 }
-            }
-            8 => {
-                // backspace
-                if onkeydown_next_chip.is_empty() && !onkeydown_current_chips.is_empty() {
-                    let mut chips = (*onkeydown_current_chips).clone();
+                "ArrowDown" | "ArrowUp" => {
+                    listbox.handle_search_key(&event, &suggestions.entries);
+                }
+                // Without a highlighted suggestion, Enter takes the first one,
+                // unless any text can be entered as a chip.
+                "Enter" if highlighted.is_some() || (!free_form && first_enabled.is_some()) => {
+                    event.prevent_default();
+
+                    if let Some(index) = highlighted.or(first_enabled) {
+                        choose(index);
+                    }
+                }
+                "Enter" if free_form => {
+                    event.prevent_default();
+                    add_chips(vec![next_chip], String::new());
+                }
+                "Tab" if free_form && !next_chip.trim().is_empty() => {
+                    event.prevent_default();
+                    add_chips(vec![next_chip], String::new());
+                }
+                "Escape" if open => {
+                    event.prevent_default();
+                    dropdown_open.set(false);
+                    listbox.highlight(None);
+                }
+                "Escape" if can_clear => {
+                    event.prevent_default();
+                    clear.emit(());
+                }
+                "Backspace" if next_chip.is_empty() && !state.is_empty() => {
+                    let mut chips = (*state).clone();
                     chips.remove(chips.len() - 1);
-                    onkeydown_current_chips.set(chips);
+                    state.set(chips);
+                }
+                "ArrowLeft" if caret_at_start(&input_node) && !state.is_empty() => {
+                    event.prevent_default();
+                    focus_element_by_id(&chip_id(&id, state.len() - 1));
+                }
+                _ if free_form
+                    && key.chars().count() == 1
+                    && key.chars().all(|c| delimiters.contains(&c)) =>
+                {
+                    event.prevent_default();
+                    add_chips(vec![next_chip], String::new());
                 }
+                _ => {}
             }
-            _ => {}
         }
     };
 
+    // The chip being edited and its edited text. This is not kept in state,
+    // so that finishing an edit is not repeated when the edit input loses
+    // focus as a result.
+    let edit = use_mut_ref(|| None::<(usize, String)>);
+    let edit_node = use_node_ref();
+    let force_update = use_force_update();
+    let editing_index = edit.borrow().as_ref().map(|(index, _)| *index);
+    {
+        let edit_node = edit_node.clone();
+        use_effect_with_deps(
+            move |editing_index| {
+                if editing_index.is_some() {
+                    if let Some(input) = edit_node.cast::<HtmlInputElement>() {
+                        let _ = input.focus();
+                        input.select();
+                    }
+                }
+            },
+            editing_index,
+        );
+    }
+    let start_edit = {
+        let edit = edit.clone();
+        let force_update = force_update.clone();
+        move |index: usize, label: String| {
+            if editable && !disabled && edit.borrow().is_none() && T::from_text(&label).is_some() {
+                *edit.borrow_mut() = Some((index, label));
+                force_update.force_update();
+            }
+        }
+    };
+    // Edited text that is blank removes the chip, while text duplicating
+    // another chip is discarded.
+    let finish_edit = {
+        let state = state.clone();
+        let edit = edit.clone();
+        move |commit: bool| {
+            let finished = edit.borrow_mut().take();
+
+            if let Some((index, text)) = finished {
+                let mut chips = (*state).clone();
+                let text = text.trim();
+
+                if commit && index < chips.len() {
+                    if text.is_empty() {
+                        chips.remove(index);
+                    } else if let Some(chip) = T::from_text(text) {
+                        if !chips
+                            .iter()
+                            .enumerate()
+                            .any(|(other, other_chip)| other != index && *other_chip == chip)
+                        {
+                            chips[index] = chip;
+                        }
+                    }
+                }
+
+                if chips != *state {
+                    state.set(chips);
+                }
+
+                force_update.force_update();
+            }
+        }
+    };
+    let dragged = use_state(|| None::<usize>);
+
     let chip_list = (*state)
         .iter()
+        .zip(chip_labels.iter())
         .enumerate()
-        .map(|(index, this_chip)| {
+        .map(|(index, (this_chip, this_label))| {
             let local_chips_state = state.clone();
+            let chip_error = chip_errors[index].clone();
+            let chip_count = state.len();
+
+            if editing_index == Some(index) {
+                let edit_text = edit
+                    .borrow()
+                    .as_ref()
+                    .map(|(_, text)| text.clone())
+                    .unwrap_or_default();
+                let on_edit_input = {
+                    let edit = edit.clone();
+                    move |event: InputEvent| {
+                        let value = input_event_value(event);
+
+                        if let Some((_, text)) = edit.borrow_mut().as_mut() {
+                            *text = value;
+                        }
+                    }
+                };
+                let on_edit_keydown = {
+                    let id = id.clone();
+                    let input_node = input_node.clone();
+                    let finish_edit = finish_edit.clone();
+                    move |event: KeyboardEvent| {
+                        event.stop_propagation();
+
+                        match event.key().as_str() {
+                            "Enter" => {
+                                event.prevent_default();
+                                finish_edit(true);
+                                focus_element(&input_node);
+                            }
+                            "Escape" => {
+                                event.prevent_default();
+                                finish_edit(false);
+                                focus_element_by_id(&chip_id(&id, index));
+                            }
+                            _ => {}
+                        }
+                    }
+                };
+                let on_edit_blur = {
+                    let finish_edit = finish_edit.clone();
+                    move |_| {
+                        finish_edit(true);
+                    }
+                };
+
+                return html! {
+                    <div
+                        id={chip_id(&id, index)}
+                        role="listitem"
+                        class="base-chips-chip base-chips-chip-editing"
+                    >
+                        <input
+                            ref={edit_node.clone()}
+                            type="text"
+                            value={edit_text}
+                            oninput={on_edit_input}
+                            onkeydown={on_edit_keydown}
+                            onblur={on_edit_blur}
+                            aria-label={format!("Edit {this_label}")}
+                            maxlength={max_length.to_string()}
+                            class="base-chips-chip-edit"
+                        />
+                    </div>
+                };
+            }
+
+            let content = match &render_chip {
+                Some(render_chip) => render_chip.emit(this_chip.clone()),
+                None => html! { this_label },
+            };
+            let style_class = chip_style.as_ref().map(|chip_style| {
+                let style = chip_style.emit(this_chip.clone());
+                format!("base-chips-chip-{}", style.style_name())
+            });
 
             let on_click = move |_| {
                 let mut current_chips_without_this = (*local_chips_state).clone();
                 current_chips_without_this.remove(index);
                 local_chips_state.set(current_chips_without_this);
             };
+            let on_chip_keydown = {
+                let id = id.clone();
+                let input_node = input_node.clone();
+                let on_click = on_click.clone();
+                let state = state.clone();
+                let start_edit = start_edit.clone();
+                let this_label = this_label.clone();
+                move |event: KeyboardEvent| match event.key().as_str() {
+                    // The moved chip takes the place of its neighbour, which
+                    // already has the focus.
+                    "ArrowLeft" if reorderable && event.alt_key() => {
+                        event.prevent_default();
+
+                        if index > 0 {
+                            focus_element_by_id(&chip_id(&id, index - 1));
+                            move_chip(&state, index, index - 1);
+                        }
+                    }
+                    "ArrowRight" if reorderable && event.alt_key() => {
+                        event.prevent_default();
+
+                        if index + 1 < chip_count {
+                            focus_element_by_id(&chip_id(&id, index + 1));
+                            move_chip(&state, index, index + 1);
+                        }
+                    }
+                    "ArrowLeft" => {
+                        event.prevent_default();
+                        focus_element_by_id(&chip_id(&id, index.saturating_sub(1)));
+                    }
+                    "ArrowRight" if index + 1 < chip_count => {
+                        event.prevent_default();
+                        focus_element_by_id(&chip_id(&id, index + 1));
+                    }
+                    "ArrowRight" => {
+                        event.prevent_default();
+                        focus_element(&input_node);
+                    }
+                    "Enter" | "F2" if editable => {
+                        event.prevent_default();
+                        start_edit(index, this_label.clone());
+                    }
+                    "Delete" | "Backspace" => {
+                        event.prevent_default();
+
+                        // The following chip takes this chip's place, and
+                        // with it the focus. Removing the last chip moves
+                        // focus back a chip, or to the input.
+                        if index + 1 == chip_count {
+                            if index > 0 {
+                                focus_element_by_id(&chip_id(&id, index - 1));
+                            } else {
+                                focus_element(&input_node);
+                            }
+                        }
+
+                        on_click(());
+                    }
+                    _ => {}
+                }
+            };
+            let on_chip_dblclick = {
+                let start_edit = start_edit.clone();
+                let this_label = this_label.clone();
+                move |_| {
+                    start_edit(index, this_label.clone());
+                }
+            };
+            let on_drag_start = {
+                let dragged = dragged.clone();
+                let this_label = this_label.clone();
+                move |event: DragEvent| {
+                    if let Some(data_transfer) = event.data_transfer() {
+                        data_transfer.set_effect_allowed("move");
+                        let _ = data_transfer.set_data("text/plain", &this_label);
+                    }
+
+                    dragged.set(Some(index));
+                }
+            };
+            let on_drag_over = {
+                let dragged = dragged.clone();
+                move |event: DragEvent| {
+                    if dragged.is_some() {
+                        event.prevent_default();
+                    }
+                }
+            };
+            let on_drop = {
+                let dragged = dragged.clone();
+                let state = state.clone();
+                move |event: DragEvent| {
+                    if let Some(from) = *dragged {
+                        event.prevent_default();
+                        move_chip(&state, from, index);
+                        dragged.set(None);
+                    }
+                }
+            };
+            let on_drag_end = {
+                let dragged = dragged.clone();
+                move |_| {
+                    dragged.set(None);
+                }
+            };
+            let draggable = reorderable && !disabled;
+            // The chips cannot be reached from the input once it is disabled
+            // at the maximum, so the first chip is reachable with Tab.
+            let tabindex = (!disabled).then_some(if at_max && index == 0 { "0" } else { "-1" });
 
             html! {
-                <div class="base-chips-chip">
-                    <span class="base-chips-chip-label">{this_chip}</span>
+                <div
+                    id={chip_id(&id, index)}
+                    role="listitem"
+                    {tabindex}
+                    title={chip_error.clone()}
+                    aria-invalid={chip_error.is_some().then_some("true")}
+                    draggable={draggable.then_some("true")}
+                    onkeydown={on_chip_keydown}
+                    ondblclick={on_chip_dblclick}
+                    ondragstart={draggable.then_some(on_drag_start)}
+                    ondragover={draggable.then_some(on_drag_over)}
+                    ondrop={draggable.then_some(on_drop)}
+                    ondragend={draggable.then_some(on_drag_end)}
+                    class={classes!(
+                        "base-chips-chip",
+                        style_class,
+                        chip_error.is_some().then_some("base-chips-chip-invalid"),
+                        (*dragged == Some(index)).then_some("base-chips-chip-dragging"),
+                    )}
+                >
+                    <span class="base-chips-chip-label">{content}</span>
                     <IconButton
                         name="xmark-solid"
                         size={IconButtonSize::Small}
+                        label={format!("Remove {this_label}")}
                         {disabled}
                         {on_click}
                         class="base-chips-chip-remove"
@@ -202,7 +913,7 @@ pub fn Chips(props: &ChipsProps) -> Html {
         html! {}
     } else {
         html! {
-            <div class="base-chips-chip-list">
+            <div role="list" class="base-chips-chip-list">
                 {chip_list}
             </div>
         }
@@ -215,48 +926,134 @@ pub fn Chips(props: &ChipsProps) -> Html {
             dropdown_open_local.set(false);
         }
     });
+    let onfocusout = {
+        let chips_node_local = chips_node.clone();
+        move |event: FocusEvent| {
+            if focus_leaving(&event, &chips_node_local) {
+                on_blur.emit(());
+            }
+        }
+    };
 
+    let virtual_list = use_virtual_list(
+        possible_options.len(),
+        row_height,
+        highlighted.filter(|index| *index < possible_options.len()),
+    );
+    let option_range = virtual_list.range();
+    let chip_option_html = |index: usize, content: Html, class: &'static str| {
+        let disabled = entries[index].disabled;
+        let onclick = {
+            let choose = choose.clone();
+            move |_| {
+                choose(index);
+            }
+        };
+        let onmouseenter = {
+            let listbox = listbox.clone();
+            move |_| {
+                if !disabled {
+                    listbox.highlight(Some(index));
+                }
+            }
+        };
+
+        html! {
+            <div
+                id={listbox.option_id(index)}
+                role="option"
+                aria-selected="false"
+                aria-disabled={disabled.to_string()}
+                {onclick}
+                {onmouseenter}
+                class={classes!(
+                    "base-chips-option",
+                    class,
+                    disabled.then_some("base-chips-option-disabled"),
+                    (highlighted == Some(index)).then_some("base-chips-option-highlighted"),
+                )}
+            >
+                {content}
+            </div>
+        }
+    };
     let chip_options = possible_options
         .iter()
-        .map(|this_option| {
-            let this_option = this_option.clone();
-            let this_option_html = this_option.clone();
-            let option_current_chips_state = state.clone();
-            let option_next_chip_state = next_chip_state.clone();
-            let on_option_click = move |_| {
-                let mut option_chips = (*option_current_chips_state).clone();
-                option_chips.push(this_option.clone());
-                option_current_chips_state.set(option_chips);
-                option_next_chip_state.set(String::new());
+        .enumerate()
+        .skip(option_range.start)
+        .take(option_range.len())
+        .map(|(index, (this_option, matches))| {
+            let content = match &render_option {
+                Some(render_option) => render_option.emit(this_option.clone()),
+                None => highlighted_label(&this_option.label(), matches),
             };
 
-            html! {
-                <div onclick={on_option_click} class={classes!("base-chips-option")}>
-                    {this_option_html}
-                </div>
-            }
+            chip_option_html(index, content, "")
         })
         .collect::<Html>();
 
-    let conditional_chip_options = if possible_options.is_empty() {
+    let on_popup_scroll = {
+        let remote = remote.clone();
+        let virtual_list = virtual_list.clone();
+        move |event: Event| {
+            virtual_list.on_scroll();
+
+            if scrolled_near_bottom(&event, LOAD_MORE_THRESHOLD_PX) {
+                remote.load_more();
+            }
+        }
+    };
+
+    let create_option = create_value.as_ref().map(|value| {
+        chip_option_html(
+            possible_options.len(),
+            html! { create_option_label(value) },
+            "base-chips-option-create",
+        )
+    });
+
+    let conditional_chip_options = if possible_options.is_empty()
+        && create_option.is_none()
+        && !show_status
+        && !show_empty
+    {
         html! {}
     } else {
         html! {
             <div class="base-chips-options-dropdown">
-                <div class="base-chips-options-popup">
-                    {chip_options}
+                <div
+                    id={listbox.id()}
+                    role="listbox"
+                    ref={virtual_list.viewport()}
+                    onmousedown={|event: MouseEvent| event.prevent_default()}
+                    onscroll={on_popup_scroll}
+                    class="base-chips-options-popup"
+                >
+                    {virtual_list.render(chip_options)}
+                    {create_option}
+                    {dropdown_status_html("base-chips-options", remote.loading(), remote.error(), show_empty)}
                 </div>
             </div>
         }
     };
 
     html! {
-        <div class={classes!("base-chips-container", disabled.then_some("base-chips-container-disabled"), (*dropdown_open).then_some("base-chips-container-open"), error.as_ref().map(|_| "base-chips-container-invalid"))}>
-            <label for={id.clone()} class="base-chips-label">{label}</label>
-            <div ref={chips_node} class="base-chips">
+        <FieldWrapper
+            id={id.clone()}
+            {label}
+            {required}
+            {show_optional}
+            {help}
+            error={error.clone()}
+            {disabled}
+            class={classes!("base-chips-container", disabled.then_some("base-chips-container-disabled"), (*dropdown_open).then_some("base-chips-container-open"), error.as_ref().map(|_| "base-chips-container-invalid"))}
+            label_class="base-chips-label"
+        >
+            <div ref={chips_node} {onfocusout} class="base-chips">
                 <div class="base-chips-inner">
                     {conditional_chip_list}
                     <input
+                        ref={input_node}
                         type="text"
                         value={next_chip}
                         {id}
@@ -264,14 +1061,111 @@ pub fn Chips(props: &ChipsProps) -> Html {
                         {onfocusin}
                         {onkeydown}
                         {placeholder}
-                        {disabled}
+                        disabled={disabled || at_max}
                         maxlength={max_length.to_string()}
+                        role="combobox"
+                        aria-autocomplete="list"
+                        aria-expanded={open.to_string()}
+                        aria-controls={listbox.id()}
+                        aria-activedescendant={highlighted.filter(|_| open).map(|index| listbox.option_id(index))}
                         class="base-chips-input"
                     />
+                    {clear_button_html(can_clear, disabled, clear)}
                 </div>
                 {conditional_chip_options}
             </div>
-            <Error message={error} size={ErrorSize::Small} />
-        </div>
+        </FieldWrapper>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| (*value).to_owned()).collect()
+    }
+
+    #[test]
+    fn split_chips_keeps_typed_rest() {
+        assert_eq!(
+            split_chips("rust, yew;wasm", &[',', ';'], false),
+            (strings(&["rust", " yew"]), "wasm".to_owned())
+        );
+        assert_eq!(
+            split_chips("rust,", &[',', ';'], false),
+            (strings(&["rust"]), String::new())
+        );
+        assert_eq!(
+            split_chips("rust\nyew", &[','], false),
+            (Vec::new(), "rust\nyew".to_owned())
+        );
+    }
+
+    #[test]
+    fn split_chips_completes_pasted_text() {
+        assert_eq!(
+            split_chips("rust, yew\r\nwasm\nweb", &[','], true),
+            (strings(&["rust", " yew", "", "wasm", "web"]), String::new())
+        );
+        assert_eq!(
+            split_chips("rust", &[','], true),
+            (strings(&["rust"]), String::new())
+        );
+    }
+
+    #[test]
+    fn append_chips_trims_and_drops_blank_chips() {
+        assert_eq!(
+            append_chips::<String>(&[], strings(&["  rust ", "", "   ", "yew\t"]), None),
+            strings(&["rust", "yew"])
+        );
+    }
+
+    #[test]
+    fn append_chips_drops_duplicates() {
+        assert_eq!(
+            append_chips(
+                &strings(&["rust"]),
+                strings(&["yew", "rust", " yew ", "wasm"]),
+                None
+            ),
+            strings(&["rust", "yew", "wasm"])
+        );
+    }
+
+    #[test]
+    fn append_chips_stops_at_max_chips() {
+        assert_eq!(
+            append_chips(
+                &strings(&["rust"]),
+                strings(&["yew", "", "rust", "wasm", "web"]),
+                Some(3)
+            ),
+            strings(&["rust", "yew", "wasm"])
+        );
+        assert_eq!(
+            append_chips(&strings(&["rust", "yew"]), strings(&["wasm"]), Some(2)),
+            strings(&["rust", "yew"])
+        );
+    }
+
+    #[test]
+    fn append_chips_drops_chips_not_created_from_text() {
+        assert_eq!(
+            append_chips::<&'static str>(&["rust"], strings(&["yew"]), None),
+            vec!["rust"]
+        );
+    }
+
+    #[test]
+    fn pasted_chips_are_split_and_appended() {
+        let (values, rest) = split_chips("rust; yew\nrust,wasm", &[',', ';'], true);
+
+        assert_eq!(rest, "");
+        assert_eq!(
+            append_chips(&strings(&["yew"]), values, Some(3)),
+            strings(&["yew", "rust", "wasm"])
+        );
     }
 }
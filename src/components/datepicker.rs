@@ -133,6 +133,50 @@ fn check_state(
     }
 }
 
+/// Returns the year, month and day strings displayed for a date, which are
+/// empty when there is no date.
+fn date_strings(value: Option<NaiveDate>) -> (String, String, String) {
+    match value {
+        Some(date) => (
+            year_to_string(date.year()),
+            month_to_string(date.month()),
+            day_to_string(date.day()),
+        ),
+        None => Default::default(),
+    }
+}
+
+/// Checks whether the displayed date strings represent the given value. No
+/// value is represented by strings that do not form a date within the range.
+fn date_strings_represent(
+    year_str: &str,
+    month_str: &str,
+    day_str: &str,
+    value: Option<NaiveDate>,
+    min: &NaiveDate,
+    max: &NaiveDate,
+) -> bool {
+    match value {
+        Some(date) => parse_date(year_str, month_str, day_str) == Ok(date),
+        None => check_state(year_str, month_str, day_str, min, max).is_err(),
+    }
+}
+
+/// Gets the year, month and day strings to display in place of the current
+/// ones, if they no longer represent the value, as after a form field is
+/// reset.
+fn resynced_date_strings(
+    year_str: &str,
+    month_str: &str,
+    day_str: &str,
+    value: Option<NaiveDate>,
+    min: &NaiveDate,
+    max: &NaiveDate,
+) -> Option<(String, String, String)> {
+    (!date_strings_represent(year_str, month_str, day_str, value, min, max))
+        .then(|| date_strings(value))
+}
+
 /// Returns the name of the month.
 fn month_name(date: &NaiveDate) -> String {
     MONTHS[date.month0() as usize].to_owned()
@@ -220,7 +264,20 @@ pub fn date_picker_today() -> NaiveDate {
 #[derive(Properties, PartialEq, Clone)]
 pub struct DatePickerProps {
     /// The date picker state.
-    pub state: UseStateHandle<Option<NaiveDate>>,
+    #[prop_or_default]
+    pub state: Option<UseStateHandle<Option<NaiveDate>>>,
+    /// The date picker value. Used along with `on_change` in place of `state`.
+    #[prop_or_default]
+    pub value: Option<Option<NaiveDate>>,
+    /// The callback called when the date picker value changes.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_change: Callback<Option<NaiveDate>>,
+    /// The callback called when the date picker loses focus.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_blur: Callback<()>,
+    /// The name of the field within a surrounding form.
+    #[prop_or_default]
+    pub name: String,
     /// The date picker label.
     #[prop_or_default]
     pub label: String,
@@ -233,6 +290,12 @@ pub struct DatePickerProps {
     /// Whether a date must be picked.
     #[prop_or(false)]
     pub required: bool,
+    /// Whether to mark the date picker as optional when it is not required.
+    #[prop_or(false)]
+    pub show_optional: bool,
+    /// Help text describing the date picker.
+    #[prop_or_default]
+    pub help: String,
     /// An optional error message. This may not show at times, as it will be
     /// overridden by a different error message if validation fails.
     #[prop_or_default]
@@ -240,6 +303,13 @@ pub struct DatePickerProps {
     /// Whether the date picker is disabled.
     #[prop_or(false)]
     pub disabled: bool,
+    /// Whether the date picker can be cleared with an inline clear button or
+    /// the Escape key, leaving no date picked.
+    #[prop_or(false)]
+    pub clearable: bool,
+    /// The callback called when the date picker is cleared.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_clear: Callback<()>,
 }
 
 /// A date picker component.
@@ -247,14 +317,24 @@ pub struct DatePickerProps {
 pub fn DatePicker(props: &DatePickerProps) -> Html {
     let DatePickerProps {
         state,
+        value,
+        on_change,
+        on_blur,
+        name,
         label,
         min,
         max,
         required,
+        show_optional,
+        help,
         error,
         disabled,
+        clearable,
+        on_clear,
     } = props.clone();
 
+    let state = use_value_handle(state, value, on_change);
+
     let year_id_state = use_state(new_id);
     let year_id = (*year_id_state).clone();
     let year_node = use_node_ref();
@@ -284,26 +364,147 @@ pub fn DatePicker(props: &DatePickerProps) -> Html {
     let num_days_in_month = days_in_month(&viewing_calendar_month_state);
     let num_days_after_month = days_after_month(&viewing_calendar_month_state);
 
+    let validation = use_validation(ValidationMode::OnChange);
+
     let update_state = {
         let local_state = state.clone();
+        let local_validation = validation.clone();
         let current_year = year_value.clone();
         let current_month = month_value.clone();
         let current_day = day_value.clone();
-        move |year: Option<&str>, month: Option<&str>, day: Option<&str>| match check_state(
-            year.unwrap_or(&current_year),
-            month.unwrap_or(&current_month),
-            day.unwrap_or(&current_day),
-            &min,
-            &max,
-        ) {
-            Ok(date) => local_state.set(Some(date)),
-            Err(_) => local_state.set(None),
+        move |year: Option<&str>, month: Option<&str>, day: Option<&str>| {
+            local_validation.changed();
+
+            match check_state(
+                year.unwrap_or(&current_year),
+                month.unwrap_or(&current_month),
+                day.unwrap_or(&current_day),
+                &min,
+                &max,
+            ) {
+                Ok(date) => local_state.set(Some(date)),
+                Err(_) => local_state.set(None),
+            }
         }
     };
 
-    let error_msg = check_state(&year_value, &month_value, &day_value, &min, &max)
-        .err()
-        .or(error);
+    // A cleared date picker has no date at all, rather than an invalid one.
+    let cleared = year_value.is_empty() && month_value.is_empty() && day_value.is_empty();
+    let date_error = if cleared {
+        None
+    } else {
+        check_state(&year_value, &month_value, &day_value, &min, &max).err()
+    };
+    let form_error = use_form_registration(
+        &name,
+        FormField {
+            label: label.clone(),
+            node: year_node.clone(),
+            error_shown: error.is_some() || validation.visible(),
+            ..FormField::new(
+                FormValue::Date(*state),
+                required,
+                date_error.clone().or_else(|| error.clone()),
+            )
+        },
+    );
+    use_form_restore(&name, {
+        let state = state.clone();
+        let year_state = year_state.clone();
+        let month_state = month_state.clone();
+        let day_state = day_state.clone();
+        let year_node = year_node.clone();
+        let month_node = month_node.clone();
+        let day_node = day_node.clone();
+        move |value| {
+            if let FormValue::Date(value) = value {
+                state.set(value);
+
+                let (year_str, month_str, day_str) = date_strings(value);
+                set_inner_text(&year_node, &year_str);
+                set_inner_text(&month_node, &month_str);
+                set_inner_text(&day_node, &day_str);
+                year_state.set(year_str);
+                month_state.set(month_str);
+                day_state.set(day_str);
+            }
+        }
+    });
+    // Keep the displayed date in line with a value set from outside the
+    // picker, such as a controlled `value` or a form reset. The first render
+    // is left alone, as an empty picker starts out showing today's date.
+    {
+        let last_value = use_mut_ref(|| *state);
+        let year_state = year_state.clone();
+        let month_state = month_state.clone();
+        let day_state = day_state.clone();
+        let year_node = year_node.clone();
+        let month_node = month_node.clone();
+        let day_node = day_node.clone();
+        use_effect_with_deps(
+            move |value| {
+                let changed = *last_value.borrow() != *value;
+                *last_value.borrow_mut() = *value;
+
+                if changed {
+                    if let Some((year_str, month_str, day_str)) = resynced_date_strings(
+                        &year_state,
+                        &month_state,
+                        &day_state,
+                        *value,
+                        &min,
+                        &max,
+                    ) {
+                        set_inner_text(&year_node, &year_str);
+                        set_inner_text(&month_node, &month_str);
+                        set_inner_text(&day_node, &day_str);
+                        year_state.set(year_str);
+                        month_state.set(month_str);
+                        day_state.set(day_str);
+                    }
+                }
+
+                || ()
+            },
+            *state,
+        );
+    }
+    let error_msg = validation
+        .visible_error(date_error)
+        .or(error)
+        .or(form_error);
+
+    let clear = {
+        let state = state.clone();
+        let validation = validation.clone();
+        let year_state = year_state.clone();
+        let month_state = month_state.clone();
+        let day_state = day_state.clone();
+        let year_node = year_node.clone();
+        let month_node = month_node.clone();
+        let day_node = day_node.clone();
+        Callback::from(move |_| {
+            state.set(None);
+            set_inner_text(&year_node, "");
+            set_inner_text(&month_node, "");
+            set_inner_text(&day_node, "");
+            year_state.set(String::new());
+            month_state.set(String::new());
+            day_state.set(String::new());
+            validation.changed();
+            focus_element(&year_node);
+            on_clear.emit(());
+        })
+    };
+    let on_input_keydown = {
+        let clear = clear.clone();
+        move |event: KeyboardEvent| {
+            if clearable && !cleared && event.key() == "Escape" {
+                event.prevent_default();
+                clear.emit(());
+            }
+        }
+    };
 
     let year_on_focus_in = {
         let year_node_local = year_node.clone();
@@ -374,6 +575,17 @@ pub fn DatePicker(props: &DatePickerProps) -> Html {
         }
     };
 
+    let outer_node = use_node_ref();
+    let onfocusout = {
+        let outer_node_local = outer_node.clone();
+        move |event: FocusEvent| {
+            if focus_leaving(&event, &outer_node_local) {
+                validation.blurred();
+                on_blur.emit(());
+            }
+        }
+    };
+
     let popup_node = use_node_ref();
     use_click_away(popup_node.clone(), {
         let calendar_open_local = calendar_open.clone();
@@ -456,14 +668,20 @@ pub fn DatePicker(props: &DatePickerProps) -> Html {
         .collect::<Html>();
 
     html! {
-        <div class={classes!("base-date-picker-container", disabled.then_some("base-date-picker-container-disabled"))}>
-            <label for={year_id.clone()} class="base-date-picker-label">
-                {label}
-                <span class="base-required-mark">{required.then_some(" *").unwrap_or_default()}</span>
-            </label>
-            <div class="base-date-picker-outer">
+        <FieldWrapper
+            id={year_id.clone()}
+            {label}
+            {required}
+            {show_optional}
+            {help}
+            error={error_msg.clone()}
+            {disabled}
+            class={classes!("base-date-picker-container", disabled.then_some("base-date-picker-container-disabled"))}
+            label_class="base-date-picker-label"
+        >
+            <div ref={outer_node} {onfocusout} class="base-date-picker-outer">
                 <div class={classes!("base-date-picker", error_msg.as_ref().map(|_| "base-date-picker-invalid"))}>
-                    <div class="base-date-picker-section">
+                    <div class="base-date-picker-section" onkeydown={on_input_keydown}>
                         <span
                             ref={year_node}
                             id={year_id}
@@ -492,6 +710,7 @@ pub fn DatePicker(props: &DatePickerProps) -> Html {
                         >{day_value}</span>
                     </div>
                     <div class="base-date-picker-section" onfocusin={on_calendar_focus_in}>
+                        {clear_button_html(clearable && !cleared, disabled, clear)}
                         <IconButton name="calendar-days-solid" size={IconButtonSize::Medium} {disabled} on_click={on_calendar_button_click} />
                     </div>
                 </div>
@@ -531,7 +750,58 @@ pub fn DatePicker(props: &DatePickerProps) -> Html {
                     </div>
                 </div>
             </div>
-            <Error message={error_msg} size={ErrorSize::Small} />
-        </div>
+        </FieldWrapper>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn date_strings_represent_value() {
+        let (min, max) = (date(2000, 1, 1), date(2099, 12, 31));
+        let represent =
+            |year, month, day, value| date_strings_represent(year, month, day, value, &min, &max);
+
+        assert!(represent("2024", "02", "29", Some(date(2024, 2, 29))));
+        assert!(!represent("2024", "02", "28", Some(date(2024, 2, 29))));
+        assert!(represent("1990", "05", "01", Some(date(1990, 5, 1))));
+        assert!(represent("", "", "", None));
+        assert!(represent("2024", "02", "30", None));
+        assert!(represent("1990", "05", "01", None));
+        assert!(!represent("2024", "02", "29", None));
+    }
+
+    #[test]
+    fn date_strings_of_value() {
+        assert_eq!(
+            date_strings(Some(date(812, 3, 7))),
+            ("0812".to_owned(), "03".to_owned(), "07".to_owned())
+        );
+        assert_eq!(date_strings(None), Default::default());
+    }
+
+    #[test]
+    fn reset_date_resyncs_display() {
+        let (min, max) = (date(2000, 1, 1), date(2099, 12, 31));
+        let initial = Some(date(2024, 2, 29));
+
+        assert_eq!(
+            resynced_date_strings("2025", "02", "29", None, &min, &max),
+            None
+        );
+        assert_eq!(
+            resynced_date_strings("2025", "02", "29", initial, &min, &max),
+            Some(("2024".to_owned(), "02".to_owned(), "29".to_owned()))
+        );
+        assert_eq!(
+            resynced_date_strings("2025", "03", "01", None, &min, &max),
+            Some(Default::default())
+        );
     }
 }
@@ -1,4 +1,5 @@
 use super::*;
+use crate::util::*;
 use yew::prelude::*;
 
 /// Dialog size.
@@ -54,7 +55,15 @@ impl DialogActionsLayout {
 #[derive(Properties, PartialEq, Clone)]
 pub struct DialogProps {
     /// The dialog open state.
-    pub state: UseStateHandle<bool>,
+    #[prop_or_default]
+    pub state: Option<UseStateHandle<bool>>,
+    /// Whether the dialog is open. Used along with `on_change` in place of
+    /// `state`.
+    #[prop_or_default]
+    pub value: Option<bool>,
+    /// The callback called when the dialog open state changes.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_change: Callback<bool>,
     /// The dialog size.
     #[prop_or_default]
     pub size: DialogSize,
@@ -83,6 +92,8 @@ pub struct DialogProps {
 pub fn Dialog(props: &DialogProps) -> Html {
     let DialogProps {
         state,
+        value,
+        on_change,
         size,
         title,
         ok_label,
@@ -92,6 +103,8 @@ pub fn Dialog(props: &DialogProps) -> Html {
         children,
     } = props.clone();
 
+    let state = use_value_handle(state, value, on_change);
+
     let size_class = format!("base-dialog-{}", size.size_name());
     let actions_layout_class = format!("base-dialog-actions-{}", actions_layout.layout_name());
 
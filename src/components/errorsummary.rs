@@ -0,0 +1,79 @@
+use super::*;
+use crate::util::*;
+use yew::prelude::*;
+
+/// Error summary properties.
+#[derive(Properties, PartialEq, Clone)]
+pub struct ErrorSummaryProps {
+    /// The error summary title.
+    #[prop_or("Please correct the following errors".to_owned())]
+    pub title: String,
+}
+
+/// An error summary component. When placed within a form, this lists every
+/// field displaying an error, or every invalid field once submission has been
+/// attempted, and focuses a field when its entry is clicked.
+#[function_component]
+pub fn ErrorSummary(props: &ErrorSummaryProps) -> Html {
+    let ErrorSummaryProps { title } = props.clone();
+
+    let form = use_context::<FormContext>();
+
+    let errors = form
+        .map(|form| {
+            form.fields()
+                .iter()
+                .filter_map(|(name, field)| {
+                    let error = if form.submitted() {
+                        field.validation_error()
+                    } else {
+                        field.error.clone().filter(|_| field.error_shown)
+                    };
+
+                    error.map(|error| {
+                        let label = if field.label.is_empty() {
+                            name.clone()
+                        } else {
+                            field.label.clone()
+                        };
+
+                        (label, error, field.node.clone())
+                    })
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    let error_list = errors
+        .into_iter()
+        .map(|(label, error, node)| {
+            let onclick = move |_| {
+                scroll_to_element(&node);
+                focus_element(&node);
+            };
+
+            html! {
+                <li class="base-error-summary-item">
+                    <button type="button" {onclick} class="base-error-summary-link">
+                        <span class="base-error-summary-label">{label}</span>
+                        {": "}
+                        <span class="base-error-summary-message">{error}</span>
+                    </button>
+                </li>
+            }
+        })
+        .collect::<Vec<_>>();
+
+    html! {
+        <div role="alert" class="base-error-summary-container">
+            {(!error_list.is_empty()).then_some(html! {
+                <div class="base-error-summary">
+                    <h4 class="base-error-summary-title">{title}</h4>
+                    <ul class="base-error-summary-list">
+                        {error_list}
+                    </ul>
+                </div>
+            })}
+        </div>
+    }
+}
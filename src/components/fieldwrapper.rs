@@ -0,0 +1,118 @@
+use super::*;
+use yew::prelude::*;
+
+/// Field wrapper properties.
+#[derive(Properties, PartialEq, Clone)]
+pub struct FieldWrapperProps {
+    /// The ID of the element the label refers to.
+    #[prop_or_default]
+    pub id: String,
+    /// The field label. Will not be created if empty and the field is not
+    /// required.
+    #[prop_or_default]
+    pub label: String,
+    /// Whether the field is required to be filled out.
+    #[prop_or(false)]
+    pub required: bool,
+    /// Whether to mark the field as optional when it is not required.
+    #[prop_or(false)]
+    pub show_optional: bool,
+    /// Help text describing the field.
+    #[prop_or_default]
+    pub help: String,
+    /// The maximum number of characters allowed. A character counter is
+    /// shown if this is set.
+    #[prop_or_default]
+    pub max_length: Option<usize>,
+    /// The current number of characters, used by the character counter.
+    #[prop_or(0)]
+    pub length: usize,
+    /// Whether validation of the field is pending.
+    #[prop_or(false)]
+    pub pending: bool,
+    /// An optional error message.
+    #[prop_or_default]
+    pub error: Option<String>,
+    /// Whether the field is disabled.
+    #[prop_or(false)]
+    pub disabled: bool,
+    /// Classes to apply to the field container.
+    #[prop_or_default]
+    pub class: Classes,
+    /// Classes to apply to the field label.
+    #[prop_or_default]
+    pub label_class: Classes,
+    /// The field input elements.
+    pub children: Children,
+}
+
+/// A field wrapper component, providing a consistent layout for a field's
+/// label, help text, character counter and error message.
+#[function_component]
+pub fn FieldWrapper(props: &FieldWrapperProps) -> Html {
+    let FieldWrapperProps {
+        id,
+        label,
+        required,
+        show_optional,
+        help,
+        max_length,
+        length,
+        pending,
+        error,
+        disabled,
+        class,
+        label_class,
+        children,
+    } = props.clone();
+
+    let optional = show_optional && !required;
+
+    html! {
+        <div class={classes!("base-field", disabled.then_some("base-field-disabled"), class)}>
+            {(!label.is_empty() || required).then_some(html! {
+                <label for={id} class={classes!("base-field-label", label_class)}>
+                    {label}
+                    <span class="base-required-mark">{if required { " *" } else { "" }}</span>
+                    {optional.then_some(html! {
+                        <span class="base-optional-mark">{" (optional)"}</span>
+                    })}
+                </label>
+            })}
+            {children}
+            {pending.then_some(html! {
+                <div class="base-field-pending">
+                    <Spinner size={SpinnerSize::Small} center={false} />
+                </div>
+            })}
+            {(!help.is_empty() || max_length.is_some()).then_some(html! {
+                <div class="base-field-details">
+                    <span class="base-field-help">{help}</span>
+                    {max_length.map(|max_length| html! {
+                        <span class={classes!("base-field-counter", (length > max_length).then_some("base-field-counter-over"))}>
+                            {format!("{length}/{max_length}")}
+                        </span>
+                    })}
+                </div>
+            })}
+            <Error message={error} size={ErrorSize::Small} />
+        </div>
+    }
+}
+
+/// Renders the inline button that clears a field, if the field is clearable
+/// and currently has a value to clear.
+pub(crate) fn clear_button_html(visible: bool, disabled: bool, on_clear: Callback<()>) -> Html {
+    visible
+        .then_some(html! {
+            <IconButton
+                name="xmark-solid"
+                size={IconButtonSize::Small}
+                label="Clear"
+                {disabled}
+                on_click={on_clear}
+                class="base-field-clear"
+            />
+        })
+        .unwrap_or_default()
+}
@@ -0,0 +1,674 @@
+use super::*;
+use crate::util::*;
+use chrono::NaiveDate;
+use gloo_timers::callback::Timeout;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::rc::Rc;
+use web_sys::Storage;
+use yew::prelude::*;
+
+/// The value of a field within a form.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FormValue {
+    /// A text value.
+    Text(String),
+    /// A numeric value.
+    Number(f64),
+    /// A boolean value.
+    Bool(bool),
+    /// An optional date value.
+    Date(Option<NaiveDate>),
+    /// The index of an optionally selected radio button.
+    Selection(Option<usize>),
+    /// The label of an optionally chosen option. Labels are used rather than
+    /// indices so that restored values are unaffected by changes to the
+    /// order of the options.
+    Choice(Option<String>),
+    /// The labels of any number of chosen options.
+    Choices(Vec<String>),
+    /// A list of text values.
+    List(Vec<String>),
+}
+
+impl FormValue {
+    /// Whether the value should be considered empty when a field is required.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Self::Text(value) => value.is_empty(),
+            Self::Number(_) => false,
+            Self::Bool(value) => !*value,
+            Self::Date(value) => value.is_none(),
+            Self::Selection(value) => value.is_none(),
+            Self::Choice(value) => value.is_none(),
+            Self::Choices(value) => value.is_empty(),
+            Self::List(value) => value.is_empty(),
+        }
+    }
+}
+
+/// Where a form's draft values are persisted.
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum DraftStorage {
+    /// Drafts are not persisted.
+    #[default]
+    None,
+    /// Drafts are persisted to `localStorage`, surviving browser restarts.
+    Local,
+    /// Drafts are persisted to `sessionStorage`, surviving page reloads
+    /// within the same tab.
+    Session,
+}
+
+impl DraftStorage {
+    /// Gets the storage area corresponding to the draft storage option, if
+    /// it is available.
+    pub fn storage(&self) -> Option<Storage> {
+        match *self {
+            Self::None => None,
+            Self::Local => local_storage(),
+            Self::Session => session_storage(),
+        }
+    }
+}
+
+/// A fieldless enum whose variants can be chosen from a `Select` within a
+/// form. This can be implemented with `#[derive(YinForm)]`.
+pub trait FormEnum: Clone + PartialEq + 'static {
+    /// Gets every variant, in the order they should be displayed.
+    fn variants() -> Vec<Self>;
+
+    /// Gets the label displayed for the variant.
+    fn label(&self) -> String;
+}
+
+/// The state of a form generated with `#[derive(YinForm)]`. Each field change
+/// is applied to the latest value rather than to the value at the time of the
+/// last render, so that changes emitted together, such as when a draft is
+/// restored, are not lost.
+#[doc(hidden)]
+pub struct DerivedFormState<T> {
+    /// The latest value of the form state.
+    latest: Rc<RefCell<T>>,
+    /// The callback called with each updated value.
+    on_change: Callback<T>,
+}
+
+impl<T: Clone> DerivedFormState<T> {
+    /// Creates a new derived form state.
+    pub fn new(latest: Rc<RefCell<T>>, on_change: Callback<T>) -> Self {
+        Self { latest, on_change }
+    }
+
+    /// Applies a change to the latest value, emitting the updated value.
+    pub fn update(&self, change: impl FnOnce(&mut T)) {
+        let value = {
+            let mut latest = self.latest.borrow_mut();
+            change(&mut latest);
+            latest.clone()
+        };
+
+        self.on_change.emit(value);
+    }
+}
+
+impl<T> Clone for DerivedFormState<T> {
+    fn clone(&self) -> Self {
+        Self {
+            latest: self.latest.clone(),
+            on_change: self.on_change.clone(),
+        }
+    }
+}
+
+/// Tracks the state of a form generated with `#[derive(YinForm)]`.
+#[doc(hidden)]
+#[hook]
+pub fn use_derived_form_state<T>(state: &UseStateHandle<T>) -> DerivedFormState<T>
+where
+    T: Clone + 'static,
+{
+    let latest = use_mut_ref(|| (**state).clone());
+    *latest.borrow_mut() = (**state).clone();
+
+    let state = state.clone();
+    DerivedFormState::new(latest, Callback::from(move |value| state.set(value)))
+}
+
+/// The aggregated values of all fields within a form, keyed by field name.
+pub type FormValues = HashMap<String, FormValue>;
+
+/// The state of a single field registered with a form.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormField {
+    /// The current value of the field.
+    pub value: FormValue,
+    /// Whether the field is required to be filled out.
+    pub required: bool,
+    /// The error message reported by the field itself, if any.
+    pub error: Option<String>,
+    /// Whether validation of the field is still pending.
+    pub pending: bool,
+    /// Whether the field is displaying its error itself. Fields that wait
+    /// for the user to interact with them before displaying errors unset
+    /// this until then.
+    pub error_shown: bool,
+    /// The field label, used when referring to the field.
+    pub label: String,
+    /// The element to focus when navigating to the field.
+    pub node: NodeRef,
+    /// Whether the field's value is persisted in drafts. This is unset for
+    /// fields whose values cannot be restored.
+    pub persist: bool,
+}
+
+impl FormField {
+    /// Creates a new form field state.
+    pub fn new(value: FormValue, required: bool, error: Option<String>) -> Self {
+        Self {
+            value,
+            required,
+            error,
+            pending: false,
+            error_shown: true,
+            label: String::new(),
+            node: NodeRef::default(),
+            persist: true,
+        }
+    }
+
+    /// Gets the validation error for the field, taking into account both the
+    /// error reported by the field and whether a required value is missing.
+    pub fn validation_error(&self) -> Option<String> {
+        self.error.clone().or_else(|| {
+            (self.required && self.value.is_empty()).then(|| "This field is required".to_owned())
+        })
+    }
+
+    /// Whether the field is valid.
+    pub fn is_valid(&self) -> bool {
+        self.validation_error().is_none()
+    }
+}
+
+/// The state of a form.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FormState {
+    /// The registered fields, in order of registration.
+    fields: Vec<(String, FormField)>,
+    /// The value of each field when it was first registered.
+    initial: Rc<FormValues>,
+    /// Whether submission of the form has been attempted.
+    submitted: bool,
+    /// The most recently restored field values.
+    restored: Rc<FormValues>,
+    /// The number of times field values have been restored.
+    restorations: usize,
+}
+
+impl FormState {
+    /// Gets a registered field by name.
+    pub fn field(&self, name: &str) -> Option<&FormField> {
+        self.fields
+            .iter()
+            .find_map(|(field_name, field)| (field_name == name).then_some(field))
+    }
+
+    /// Gets all registered fields, in order of registration.
+    pub fn fields(&self) -> &[(String, FormField)] {
+        &self.fields
+    }
+
+    /// Gets the current values of all registered fields.
+    pub fn values(&self) -> FormValues {
+        self.fields
+            .iter()
+            .map(|(name, field)| (name.clone(), field.value.clone()))
+            .collect()
+    }
+
+    /// Gets the current values of all registered fields that are persisted
+    /// in drafts.
+    pub fn draft_values(&self) -> FormValues {
+        self.fields
+            .iter()
+            .filter(|(_, field)| field.persist)
+            .map(|(name, field)| (name.clone(), field.value.clone()))
+            .collect()
+    }
+
+    /// Whether the value of any registered field differs from the value it
+    /// was first registered with.
+    pub fn is_dirty(&self) -> bool {
+        self.fields
+            .iter()
+            .any(|(name, field)| self.initial.get(name) != Some(&field.value))
+    }
+
+    /// Whether every registered field is valid.
+    pub fn is_valid(&self) -> bool {
+        self.fields.iter().all(|(_, field)| field.is_valid())
+    }
+
+    /// Whether validation of any registered field is still pending.
+    pub fn is_pending(&self) -> bool {
+        self.fields.iter().any(|(_, field)| field.pending)
+    }
+
+    /// Whether submission of the form has been attempted.
+    pub fn submitted(&self) -> bool {
+        self.submitted
+    }
+
+    /// Gets the most recently restored value of a field, if any.
+    pub fn restored(&self, name: &str) -> Option<&FormValue> {
+        self.restored.get(name)
+    }
+
+    /// Gets the number of times field values have been restored.
+    pub fn restorations(&self) -> usize {
+        self.restorations
+    }
+}
+
+/// An action that updates the state of a form.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormAction {
+    /// Registers a field, or updates an already registered field.
+    Register(String, FormField),
+    /// Removes a registered field.
+    Unregister(String),
+    /// Marks the form as having been submitted.
+    Submit,
+    /// Restores the values of the given fields, such as from a saved draft.
+    Restore(FormValues),
+}
+
+impl Reducible for FormState {
+    type Action = FormAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut state = (*self).clone();
+
+        match action {
+            FormAction::Register(name, field) => {
+                match state
+                    .fields
+                    .iter_mut()
+                    .find(|(field_name, _)| *field_name == name)
+                {
+                    Some((_, existing)) => *existing = field,
+                    None => {
+                        if !state.initial.contains_key(&name) {
+                            Rc::make_mut(&mut state.initial)
+                                .insert(name.clone(), field.value.clone());
+                        }
+
+                        state.fields.push((name, field));
+                    }
+                }
+            }
+            FormAction::Unregister(name) => {
+                state.fields.retain(|(field_name, _)| *field_name != name);
+            }
+            FormAction::Submit => {
+                state.submitted = true;
+            }
+            FormAction::Restore(values) => {
+                state.restored = Rc::new(values);
+                state.restorations += 1;
+            }
+        }
+
+        Rc::new(state)
+    }
+}
+
+/// The form context made available to fields within a form.
+pub type FormContext = UseReducerHandle<FormState>;
+
+/// Registers a field with the surrounding form, if there is one. Fields with
+/// an empty name are not registered. Returns the error message the form
+/// wants displayed for the field, if any.
+#[hook]
+pub fn use_form_registration(name: &str, field: FormField) -> Option<String> {
+    let form = use_context::<FormContext>();
+
+    {
+        let form = form.clone();
+        use_effect_with_deps(
+            move |(name, field): &(String, FormField)| {
+                if let Some(form) = form {
+                    if !name.is_empty() {
+                        form.dispatch(FormAction::Register(name.clone(), field.clone()));
+                    }
+                }
+            },
+            (name.to_owned(), field.clone()),
+        );
+    }
+
+    {
+        let form = form.clone();
+        use_effect_with_deps(
+            move |name: &String| {
+                let name = name.clone();
+                move || {
+                    if let Some(form) = form {
+                        if !name.is_empty() {
+                            form.dispatch(FormAction::Unregister(name));
+                        }
+                    }
+                }
+            },
+            name.to_owned(),
+        );
+    }
+
+    form.filter(|form| form.submitted())
+        .and_then(|_| field.validation_error())
+}
+
+/// Calls `restore` with a field's value whenever the surrounding form
+/// restores field values, such as from a saved draft. Fields with no
+/// restored value are left untouched.
+#[hook]
+pub fn use_form_restore<F>(name: &str, restore: F)
+where
+    F: FnOnce(FormValue) + 'static,
+{
+    let form = use_context::<FormContext>();
+    let restorations = form
+        .as_ref()
+        .map(|form| form.restorations())
+        .unwrap_or_default();
+    let value = form.and_then(|form| form.restored(name).cloned());
+
+    use_effect_with_deps(
+        move |restorations| {
+            if *restorations > 0 {
+                if let Some(value) = value {
+                    restore(value);
+                }
+            }
+        },
+        restorations,
+    );
+}
+
+/// Gets the storage key under which a form's draft is persisted.
+fn draft_key(id: &str) -> String {
+    format!("yin-form-draft-{id}")
+}
+
+/// Loads a form's persisted draft, if there is one.
+fn load_draft(storage: &Storage, id: &str) -> Option<FormValues> {
+    storage_get(storage, &draft_key(id))
+        .and_then(|json| serde_json::from_str::<FormValues>(&json).ok())
+        .filter(|values| !values.is_empty())
+}
+
+/// Form properties.
+#[derive(Properties, PartialEq, Clone)]
+pub struct FormProps {
+    /// The callback called with the aggregated field values when the form is
+    /// submitted and every field is valid, with no validation pending.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_submit: Callback<FormValues>,
+    /// The submit button label. Will not be created if empty.
+    #[prop_or_default]
+    pub submit_label: String,
+    /// The form ID, used as the key for the form's persisted draft. Drafts
+    /// are not persisted if empty.
+    #[prop_or_default]
+    pub id: String,
+    /// Where in-progress field values are persisted.
+    #[prop_or_default]
+    pub draft_storage: DraftStorage,
+    /// The time in milliseconds to wait after a field value changes before
+    /// persisting the draft.
+    #[prop_or(500)]
+    pub draft_debounce_ms: u32,
+    /// The message displayed when offering to restore a persisted draft.
+    #[prop_or("You have unsaved changes from a previous visit. Would you like to restore them?".to_owned())]
+    pub restore_prompt: String,
+    /// Elements within the form.
+    pub children: Children,
+}
+
+/// A form component. Fields within the form register themselves by name,
+/// and submission is blocked while any of them are invalid.
+#[function_component]
+pub fn Form(props: &FormProps) -> Html {
+    let FormProps {
+        on_submit,
+        submit_label,
+        id,
+        draft_storage,
+        draft_debounce_ms,
+        restore_prompt,
+        children,
+    } = props.clone();
+
+    let form = use_reducer_eq(FormState::default);
+    let storage = (!id.is_empty()).then(|| draft_storage.storage()).flatten();
+    let draft = use_state({
+        let storage = storage.clone();
+        let id = id.clone();
+        move || storage.and_then(|storage| load_draft(&storage, &id))
+    });
+    let save_timeout = use_mut_ref(|| None::<Timeout>);
+
+    {
+        let values = form.draft_values().into_iter().collect::<BTreeMap<_, _>>();
+        let values_json = serde_json::to_string(&values).unwrap_or_default();
+        let dirty = form.is_dirty();
+        // Saving is paused while the user decides whether to restore a draft,
+        // so that the draft is not overwritten.
+        let storage = storage.clone().filter(|_| draft.is_none());
+        let save_timeout = save_timeout.clone();
+        use_effect_with_deps(
+            move |(values_json, id, _, dirty)| {
+                if let Some(storage) = storage {
+                    let key = draft_key(id);
+
+                    // Untouched forms have nothing worth restoring.
+                    if *dirty {
+                        let values_json = values_json.clone();
+                        *save_timeout.borrow_mut() =
+                            Some(Timeout::new(draft_debounce_ms, move || {
+                                storage_set(&storage, &key, &values_json);
+                            }));
+                    } else {
+                        save_timeout.borrow_mut().take(); // timeout is cancelled when dropped
+                        storage_remove(&storage, &key);
+                    }
+                }
+            },
+            (values_json, id.clone(), draft.is_none(), dirty),
+        );
+    }
+
+    let clear_draft = {
+        let storage = storage.clone();
+        let id = id.clone();
+        move || {
+            save_timeout.borrow_mut().take(); // timeout is cancelled when dropped
+
+            if let Some(storage) = &storage {
+                storage_remove(storage, &draft_key(&id));
+            }
+        }
+    };
+
+    let submit = {
+        let form = form.clone();
+        move || {
+            form.dispatch(FormAction::Submit);
+
+            if form.is_valid() && !form.is_pending() {
+                clear_draft();
+                on_submit.emit(form.values());
+            }
+        }
+    };
+
+    let on_restore_click = {
+        let form = form.clone();
+        let draft = draft.clone();
+        move |_| {
+            if let Some(values) = (*draft).clone() {
+                form.dispatch(FormAction::Restore(values));
+            }
+
+            draft.set(None);
+        }
+    };
+    let on_discard_click = {
+        let draft = draft.clone();
+        move |_| {
+            if let Some(storage) = &storage {
+                storage_remove(storage, &draft_key(&id));
+            }
+
+            draft.set(None);
+        }
+    };
+
+    let onsubmit = {
+        let submit = submit.clone();
+        move |event: SubmitEvent| {
+            event.prevent_default();
+            submit();
+        }
+    };
+    let on_submit_click = move |_| {
+        submit();
+    };
+
+    html! {
+        <form {onsubmit} novalidate={true} class="base-form">
+            {draft.is_some().then_some(html! {
+                <div role="status" class="base-form-draft">
+                    <span class="base-form-draft-message">{restore_prompt}</span>
+                    <div class="base-form-draft-actions">
+                        <Button
+                            text="Restore"
+                            style={ButtonStyle::Primary}
+                            on_click={on_restore_click}
+                        />
+                        <Button
+                            text="Discard"
+                            style={ButtonStyle::Secondary}
+                            on_click={on_discard_click}
+                        />
+                    </div>
+                </div>
+            })}
+            <ContextProvider<FormContext> context={form}>
+                {children}
+            </ContextProvider<FormContext>>
+            {(!submit_label.is_empty()).then_some(html! {
+                <div class="base-form-actions">
+                    <Button
+                        text={submit_label}
+                        style={ButtonStyle::Primary}
+                        on_click={on_submit_click}
+                    />
+                </div>
+            })}
+        </form>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn register(state: Rc<FormState>, name: &str, field: FormField) -> Rc<FormState> {
+        state.reduce(FormAction::Register(name.to_owned(), field))
+    }
+
+    fn text_field(value: &str) -> FormField {
+        FormField::new(FormValue::Text(value.to_owned()), false, None)
+    }
+
+    #[test]
+    fn derived_form_state_keeps_changes_emitted_together() {
+        #[derive(Debug, Clone, Default, PartialEq)]
+        struct Profile {
+            name: String,
+            age: u8,
+        }
+
+        let emitted = Rc::new(RefCell::new(Vec::new()));
+        let state = DerivedFormState::new(Rc::new(RefCell::new(Profile::default())), {
+            let emitted = emitted.clone();
+            Callback::from(move |value| emitted.borrow_mut().push(value))
+        });
+
+        // Both changes are made from the same render, as when a draft is
+        // restored.
+        state
+            .clone()
+            .update(|profile| profile.name = "Ferris".to_owned());
+        state.update(|profile| profile.age = 7);
+
+        assert_eq!(
+            emitted.borrow().last(),
+            Some(&Profile {
+                name: "Ferris".to_owned(),
+                age: 7,
+            })
+        );
+    }
+
+    #[test]
+    fn registered_values_are_not_dirty() {
+        let state = register(Rc::default(), "name", text_field(""));
+        let state = register(state, "size", text_field("medium"));
+
+        assert!(!state.is_dirty());
+    }
+
+    #[test]
+    fn changed_values_are_dirty_until_reverted() {
+        let state = register(Rc::default(), "name", text_field(""));
+        let state = register(state, "name", text_field("Ferris"));
+        assert!(state.is_dirty());
+
+        let state = register(state, "name", text_field(""));
+        assert!(!state.is_dirty());
+    }
+
+    #[test]
+    fn reregistered_fields_keep_their_initial_value() {
+        let state = register(Rc::default(), "name", text_field(""));
+        let state = state.reduce(FormAction::Unregister("name".to_owned()));
+        let state = register(state, "name", text_field("Ferris"));
+
+        assert!(state.is_dirty());
+    }
+
+    #[test]
+    fn draft_values_exclude_unpersisted_fields() {
+        let state = register(Rc::default(), "name", text_field("Ferris"));
+        let state = register(
+            state,
+            "remote",
+            FormField {
+                persist: false,
+                ..FormField::new(FormValue::Choice(Some("Crab".to_owned())), false, None)
+            },
+        );
+
+        let values = state.draft_values();
+        assert_eq!(values.len(), 1);
+        assert_eq!(
+            values.get("name"),
+            Some(&FormValue::Text("Ferris".to_owned()))
+        );
+    }
+}
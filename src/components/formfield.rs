@@ -0,0 +1,124 @@
+use yew::prelude::*;
+
+/// A handle to a form field's value, along with the value it started with
+/// and whether the user has interacted with it.
+pub struct UseFormFieldHandle<T> {
+    /// The current value.
+    value: UseStateHandle<T>,
+    /// The initial, or most recently committed, value.
+    initial: UseStateHandle<T>,
+    /// Whether the field has been touched.
+    touched: UseStateHandle<bool>,
+}
+
+impl<T: Clone + PartialEq + 'static> UseFormFieldHandle<T> {
+    /// Gets the current value.
+    pub fn value(&self) -> T {
+        (*self.value).clone()
+    }
+
+    /// Sets the current value.
+    pub fn set(&self, value: T) {
+        self.value.set(value);
+    }
+
+    /// Gets the state handle backing the current value. This can be passed
+    /// directly as the `state` property of any input component.
+    pub fn state(&self) -> UseStateHandle<T> {
+        self.value.clone()
+    }
+
+    /// Gets the initial, or most recently committed, value.
+    pub fn initial(&self) -> T {
+        (*self.initial).clone()
+    }
+
+    /// Whether the current value differs from the initial value.
+    pub fn is_dirty(&self) -> bool {
+        *self.value != *self.initial
+    }
+
+    /// Whether the field has been touched.
+    pub fn is_touched(&self) -> bool {
+        *self.touched
+    }
+
+    /// Marks the field as touched.
+    pub fn touch(&self) {
+        if !*self.touched {
+            self.touched.set(true);
+        }
+    }
+
+    /// Restores the initial value and marks the field as untouched. Inputs
+    /// given the field's state, including partially typed numbers and dates,
+    /// display the restored value.
+    pub fn reset(&self) {
+        self.value.set((*self.initial).clone());
+        self.touched.set(false);
+    }
+
+    /// Makes the current value the new initial value, so that the field is
+    /// no longer dirty.
+    pub fn commit(&self) {
+        self.initial.set((*self.value).clone());
+    }
+
+    /// Gets a callback that sets the current value. This can be passed as
+    /// the `on_change` property of any input component.
+    pub fn on_change(&self) -> Callback<T> {
+        let value = self.value.clone();
+        Callback::from(move |new_value| value.set(new_value))
+    }
+
+    /// Gets a callback that marks the field as touched. This can be passed as
+    /// the `on_blur` property of any input component.
+    pub fn on_blur(&self) -> Callback<()> {
+        let handle = self.clone();
+        Callback::from(move |_| handle.touch())
+    }
+
+    /// Filters an error message so that it is only shown once the field has
+    /// been touched.
+    pub fn error(&self, error: Option<String>) -> Option<String> {
+        error.filter(|_| *self.touched)
+    }
+}
+
+impl<T> Clone for UseFormFieldHandle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            initial: self.initial.clone(),
+            touched: self.touched.clone(),
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for UseFormFieldHandle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value && self.initial == other.initial && self.touched == other.touched
+    }
+}
+
+/// Creates a form field, tracking its initial value, whether it has been
+/// touched, and whether its value is dirty.
+#[hook]
+pub fn use_form_field<T, F>(init_fn: F) -> UseFormFieldHandle<T>
+where
+    T: Clone + 'static,
+    F: FnOnce() -> T,
+{
+    let initial = use_state(init_fn);
+    let value = use_state({
+        let initial = initial.clone();
+        move || (*initial).clone()
+    });
+    let touched = use_state(|| false);
+
+    UseFormFieldHandle {
+        value,
+        initial,
+        touched,
+    }
+}
@@ -12,6 +12,9 @@ pub struct IconButtonProps {
     /// The size of the icon button.
     #[prop_or_default]
     pub size: IconButtonSize,
+    /// An accessible label describing the icon button's action.
+    #[prop_or_default]
+    pub label: String,
     /// Whether the icon button is disabled.
     #[prop_or(false)]
     pub disabled: bool,
@@ -29,6 +32,7 @@ pub fn IconButton(props: &IconButtonProps) -> Html {
     let IconButtonProps {
         name,
         size,
+        label,
         disabled,
         on_click,
         mut class,
@@ -48,6 +52,7 @@ pub fn IconButton(props: &IconButtonProps) -> Html {
             type="button"
             {onclick}
             {disabled}
+            aria-label={(!label.is_empty()).then_some(label)}
             class={classes!("base-icon-button", size_class)}
         >
             <img src={svg_path} {class} />
@@ -20,7 +20,8 @@ pub enum InputType {
 }
 
 impl InputType {
-    /// Gets the HTML input element type corresponding to the current input type.
+    /// Gets the HTML input element type corresponding to the current input
+    /// type.
     pub fn html_input_type(&self) -> &'static str {
         match *self {
             Self::Text => "text",
@@ -30,13 +31,35 @@ impl InputType {
             Self::Password => "password",
         }
     }
+
+    /// Validates a value according to the input type.
+    pub fn validate(&self, value: &str) -> Result<(), String> {
+        match *self {
+            Self::Email => EmailValidator.validate(value),
+            Self::Url => UrlValidator.validate(value),
+            Self::Text | Self::Tel | Self::Password => Ok(()),
+        }
+    }
 }
 
 /// Input properties.
 #[derive(Properties, PartialEq, Clone)]
 pub struct InputProps {
     /// The input state.
-    pub state: UseStateHandle<String>,
+    #[prop_or_default]
+    pub state: Option<UseStateHandle<String>>,
+    /// The input value. Used along with `on_change` in place of `state`.
+    #[prop_or_default]
+    pub value: Option<String>,
+    /// The callback called when the input value changes.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_change: Callback<String>,
+    /// The callback called when the input loses focus.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_blur: Callback<()>,
+    /// The name of the field within a surrounding form.
+    #[prop_or_default]
+    pub name: String,
     /// The input type.
     #[prop_or_default]
     pub input_type: InputType,
@@ -49,15 +72,45 @@ pub struct InputProps {
     /// The maximum number of characters allowed.
     #[prop_or(524288)]
     pub max_length: usize,
+    /// Whether to show a character counter below the input.
+    #[prop_or(false)]
+    pub show_counter: bool,
     /// Whether the input is required to be filled out.
     #[prop_or(false)]
     pub required: bool,
+    /// Whether to mark the input as optional when it is not required.
+    #[prop_or(false)]
+    pub show_optional: bool,
+    /// Help text describing the input.
+    #[prop_or_default]
+    pub help: String,
     /// An optional error message.
     #[prop_or_default]
     pub error: Option<String>,
+    /// Validators applied to the input value.
+    #[prop_or_default]
+    pub validators: Validators<str>,
+    /// When validation errors are displayed.
+    #[prop_or_default]
+    pub validation_mode: ValidationMode,
+    /// Asynchronous validators applied to the input value. These are only
+    /// run once all other validation passes.
+    #[prop_or_default]
+    pub async_validators: AsyncValidators<str>,
+    /// The time in milliseconds to wait after the value changes before
+    /// running asynchronous validators.
+    #[prop_or(300)]
+    pub async_debounce_ms: u32,
     /// Whether the input is disabled.
     #[prop_or(false)]
     pub disabled: bool,
+    /// Whether the input can be cleared with an inline clear button or the
+    /// Escape key.
+    #[prop_or(false)]
+    pub clearable: bool,
+    /// The callback called when the input is cleared.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_clear: Callback<()>,
 }
 
 /// An input element.
@@ -65,42 +118,138 @@ pub struct InputProps {
 pub fn Input(props: &InputProps) -> Html {
     let InputProps {
         state,
+        value,
+        on_change,
+        on_blur,
+        name,
         input_type,
         label,
         placeholder,
         max_length,
+        show_counter,
         required,
+        show_optional,
+        help,
         error,
+        validators,
+        validation_mode,
+        async_validators,
+        async_debounce_ms,
         disabled,
+        clearable,
+        on_clear,
     } = props.clone();
 
+    let state = use_value_handle(state, value, on_change);
+
     let value = (*state).clone();
+    let input_node = use_node_ref();
+    let validation = use_validation(validation_mode);
+    let validation_error = input_type
+        .validate(&value)
+        .err()
+        .or_else(|| validators.validate(&value));
+    let async_validation = use_async_validation(
+        value.clone(),
+        validation_error.is_none() && !async_validators.is_empty(),
+        async_debounce_ms,
+        move |value: &String| async_validators.validate(value),
+    );
+    let validation_error = validation_error.or(async_validation.error);
+    let pending = async_validation.pending;
+    let form_error = use_form_registration(
+        &name,
+        FormField {
+            label: label.clone(),
+            node: input_node.clone(),
+            pending,
+            error_shown: error.is_some() || validation.visible(),
+            ..FormField::new(
+                FormValue::Text(value.clone()),
+                required,
+                error.clone().or_else(|| validation_error.clone()),
+            )
+        },
+    );
+    use_form_restore(&name, {
+        let state = state.clone();
+        move |value| {
+            if let FormValue::Text(value) = value {
+                state.set(value);
+            }
+        }
+    });
+    let error = error
+        .or_else(|| validation.visible_error(validation_error))
+        .or(form_error);
     let id_state = use_state(new_id);
     let id = (*id_state).clone();
     let html_input_type = input_type.html_input_type();
-    let oninput = move |event: InputEvent| {
-        let new_value = input_event_value(event);
-        state.set(new_value);
+    let oninput = {
+        let state = state.clone();
+        let validation = validation.clone();
+        move |event: InputEvent| {
+            let new_value = input_event_value(event);
+            state.set(new_value);
+            validation.changed();
+        }
+    };
+    let clear = {
+        let validation = validation.clone();
+        let input_node = input_node.clone();
+        Callback::from(move |_| {
+            state.set(String::new());
+            validation.changed();
+            focus_element(&input_node);
+            on_clear.emit(());
+        })
+    };
+    let onkeydown = {
+        let clear = clear.clone();
+        let has_value = !value.is_empty();
+        move |event: KeyboardEvent| {
+            if clearable && has_value && event.key() == "Escape" {
+                event.prevent_default();
+                clear.emit(());
+            }
+        }
+    };
+    let onblur = move |_| {
+        validation.blurred();
+        on_blur.emit(());
     };
 
     html! {
-        <div class={classes!("base-input-container", disabled.then_some("base-input-container-disabled"))}>
-            <label for={id.clone()} class="base-input-label">
-                {label}
-                <span class="base-required-mark">{required.then_some(" *").unwrap_or_default()}</span>
-            </label>
+        <FieldWrapper
+            id={id.clone()}
+            {label}
+            {required}
+            {show_optional}
+            {help}
+            max_length={show_counter.then_some(max_length)}
+            length={value.chars().count()}
+            {pending}
+            error={error.clone()}
+            {disabled}
+            class={classes!("base-input-container", disabled.then_some("base-input-container-disabled"))}
+            label_class="base-input-label"
+        >
             <input
+                ref={input_node}
                 type={html_input_type}
-                {value}
+                value={value.clone()}
                 {id}
+                {name}
                 {oninput}
+                {onkeydown}
+                {onblur}
                 {placeholder}
                 {required}
                 {disabled}
                 maxlength={max_length.to_string()}
-                class={classes!("base-input", error.clone().map(|_| "base-input-invalid"))}
+                class={classes!("base-input", error.map(|_| "base-input-invalid"))}
             />
-            <Error message={error} size={ErrorSize::Small} />
-        </div>
+            {clear_button_html(clearable && !value.is_empty(), disabled, clear)}
+        </FieldWrapper>
     }
 }
@@ -0,0 +1,520 @@
+use crate::util::*;
+use gloo_timers::callback::Timeout;
+use std::cell::RefCell;
+use std::rc::Rc;
+use yew::prelude::*;
+
+/// The time in milliseconds after which type-ahead input is discarded.
+const TYPE_AHEAD_TIMEOUT_MS: u32 = 500;
+
+/// The number of entries moved by PageUp and PageDown.
+const PAGE_ENTRIES: usize = 10;
+
+/// An entry within a listbox, as far as keyboard navigation is concerned.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ListboxEntry {
+    /// The label used to match type-ahead input.
+    pub label: String,
+    /// Whether the entry can be chosen.
+    pub disabled: bool,
+}
+
+impl ListboxEntry {
+    /// Creates a new listbox entry.
+    pub fn new(label: impl Into<String>, disabled: bool) -> Self {
+        Self {
+            label: label.into(),
+            disabled,
+        }
+    }
+}
+
+/// What a component should do in response to a key press within a listbox.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ListboxKey {
+    /// The key is not relevant to the listbox.
+    Ignored,
+    /// The key was handled by the listbox itself.
+    Handled,
+    /// The listbox should be opened.
+    Open,
+    /// The listbox should be closed.
+    Close,
+    /// The entry at the given index should be chosen.
+    Choose(usize),
+}
+
+/// Type-ahead input, along with the timeout that discards it.
+type TypeAhead = Rc<RefCell<(String, Option<Timeout>)>>;
+
+/// A handle to a listbox's highlighted entry and element IDs.
+#[derive(Clone)]
+pub(crate) struct UseListboxHandle {
+    /// The ID of the listbox element.
+    id: String,
+    /// The index of the highlighted entry.
+    highlighted: UseStateHandle<Option<usize>>,
+    /// The current type-ahead input.
+    type_ahead: TypeAhead,
+}
+
+impl UseListboxHandle {
+    /// Gets the ID of the listbox element.
+    pub fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    /// Gets the ID of the element of the entry at the given index.
+    pub fn option_id(&self, index: usize) -> String {
+        format!("{}-option-{index}", self.id)
+    }
+
+    /// Gets the index of the highlighted entry.
+    pub fn highlighted(&self) -> Option<usize> {
+        *self.highlighted
+    }
+
+    /// Highlights the entry at the given index.
+    pub fn highlight(&self, index: Option<usize>) {
+        if *self.highlighted != index {
+            self.highlighted.set(index);
+        }
+    }
+
+    /// Gets the ID of the highlighted entry's element while the listbox is
+    /// open, for use as `aria-activedescendant`.
+    pub fn active_descendant(&self, open: bool) -> Option<String> {
+        self.highlighted()
+            .filter(|_| open)
+            .map(|index| self.option_id(index))
+    }
+
+    /// Highlights the given entry when opening the listbox, falling back to
+    /// the first entry that can be chosen.
+    pub fn open_at(&self, index: Option<usize>, entries: &[ListboxEntry]) {
+        let index = index
+            .filter(|index| entries.get(*index).is_some_and(|entry| !entry.disabled))
+            .or_else(|| next_enabled(entries, None, true));
+        self.highlight(index);
+    }
+
+    /// Handles a key press within the listbox, updating the highlighted entry
+    /// and reporting what the component should do.
+    pub fn handle_key(
+        &self,
+        event: &KeyboardEvent,
+        open: bool,
+        entries: &[ListboxEntry],
+    ) -> ListboxKey {
+        let key = event.key();
+        let searching = !self.type_ahead.borrow().0.is_empty();
+
+        match key.as_str() {
+            " " if searching => return self.type_ahead(event, open, entries),
+            "Tab" if open => return ListboxKey::Close,
+            _ => {}
+        }
+
+        match navigate(&key, open, self.highlighted(), entries) {
+            Some((highlighted, action)) => {
+                self.highlight(highlighted);
+                event.prevent_default();
+                action
+            }
+            None => self.type_ahead(event, open, entries),
+        }
+    }
+
+    /// Handles a key press within a text field that filters the listbox.
+    /// Only navigation keys are handled, so that typing is unaffected.
+    pub fn handle_search_key(&self, event: &KeyboardEvent, entries: &[ListboxEntry]) -> ListboxKey {
+        let highlighted = self.highlighted();
+
+        let action = match event.key().as_str() {
+            "ArrowDown" => {
+                self.highlight(next_enabled(entries, highlighted, true).or(highlighted));
+                ListboxKey::Handled
+            }
+            "ArrowUp" => {
+                self.highlight(next_enabled(entries, highlighted, false).or(highlighted));
+                ListboxKey::Handled
+            }
+            "PageDown" => {
+                self.highlight(page_enabled(entries, highlighted, true));
+                ListboxKey::Handled
+            }
+            "PageUp" => {
+                self.highlight(page_enabled(entries, highlighted, false));
+                ListboxKey::Handled
+            }
+            "Enter" => highlighted
+                .filter(|index| entries.get(*index).is_some_and(|entry| !entry.disabled))
+                .map(ListboxKey::Choose)
+                .unwrap_or(ListboxKey::Handled),
+            "Escape" => ListboxKey::Close,
+            "Tab" => return ListboxKey::Close,
+            _ => return ListboxKey::Ignored,
+        };
+
+        event.prevent_default();
+        action
+    }
+
+    /// Handles type-ahead input, jumping to the first entry whose label
+    /// starts with the characters typed in quick succession.
+    fn type_ahead(
+        &self,
+        event: &KeyboardEvent,
+        open: bool,
+        entries: &[ListboxEntry],
+    ) -> ListboxKey {
+        let key = event.key();
+
+        if key.chars().count() != 1 || event.ctrl_key() || event.meta_key() || event.alt_key() {
+            return ListboxKey::Ignored;
+        }
+
+        event.prevent_default();
+
+        let search = {
+            let mut type_ahead = self.type_ahead.borrow_mut();
+            type_ahead.0.push_str(&key.to_lowercase());
+            let type_ahead_local = self.type_ahead.clone();
+            type_ahead.1 = Some(Timeout::new(TYPE_AHEAD_TIMEOUT_MS, move || {
+                type_ahead_local.borrow_mut().0.clear();
+            }));
+            type_ahead.0.clone()
+        };
+
+        match type_ahead_match(entries, &search, self.highlighted()) {
+            Some(index) => {
+                self.highlight(Some(index));
+
+                if open {
+                    ListboxKey::Handled
+                } else {
+                    ListboxKey::Choose(index)
+                }
+            }
+            None => ListboxKey::Handled,
+        }
+    }
+}
+
+/// Works out the entry to highlight and the action to take in response to a
+/// navigation key within a listbox, or `None` if the key is left to
+/// type-ahead.
+fn navigate(
+    key: &str,
+    open: bool,
+    highlighted: Option<usize>,
+    entries: &[ListboxEntry],
+) -> Option<(Option<usize>, ListboxKey)> {
+    let chosen = || {
+        highlighted
+            .filter(|index| entries.get(*index).is_some_and(|entry| !entry.disabled))
+            .map(ListboxKey::Choose)
+            .unwrap_or(ListboxKey::Close)
+    };
+
+    Some(match key {
+        "ArrowDown" | "ArrowUp" if !open => (highlighted, ListboxKey::Open),
+        "ArrowDown" => (
+            next_enabled(entries, highlighted, true).or(highlighted),
+            ListboxKey::Handled,
+        ),
+        "ArrowUp" => (
+            next_enabled(entries, highlighted, false).or(highlighted),
+            ListboxKey::Handled,
+        ),
+        "PageDown" if open => (
+            page_enabled(entries, highlighted, true),
+            ListboxKey::Handled,
+        ),
+        "PageUp" if open => (
+            page_enabled(entries, highlighted, false),
+            ListboxKey::Handled,
+        ),
+        "Home" if open => (next_enabled(entries, None, true), ListboxKey::Handled),
+        "End" if open => (next_enabled(entries, None, false), ListboxKey::Handled),
+        "Enter" | " " if open => (highlighted, chosen()),
+        "Enter" | " " => (highlighted, ListboxKey::Open),
+        "Escape" if open => (highlighted, ListboxKey::Close),
+        _ => return None,
+    })
+}
+
+/// Finds the entry that type-ahead input jumps to: the first entry that can
+/// be chosen whose label starts with the input, searching from the
+/// highlighted entry and wrapping around to the start of the list.
+/// Repeating a single character cycles through the entries starting with it,
+/// rather than looking for a repeated character.
+fn type_ahead_match(
+    entries: &[ListboxEntry],
+    search: &str,
+    highlighted: Option<usize>,
+) -> Option<usize> {
+    let mut chars = search.chars();
+    let first = chars.next()?;
+    let (search, start) = if chars.all(|c| c == first) {
+        (first.to_string(), highlighted.map(|index| index + 1))
+    } else {
+        (search.to_owned(), highlighted)
+    };
+    let start = start.unwrap_or(0);
+
+    (0..entries.len())
+        .map(|offset| (start + offset) % entries.len())
+        .find(|index| {
+            let entry = &entries[*index];
+            !entry.disabled && entry.label.to_lowercase().starts_with(&search)
+        })
+}
+
+/// Renders a label with the characters at the given indices highlighted, such
+/// as the characters matching a search.
+pub(crate) fn highlighted_label(label: &str, indices: &[usize]) -> Html {
+    if indices.is_empty() {
+        return html! { label };
+    }
+
+    let mut segments = Vec::<(bool, String)>::new();
+
+    for (index, c) in label.chars().enumerate() {
+        let matched = indices.contains(&index);
+
+        match segments.last_mut() {
+            Some((last_matched, segment)) if *last_matched == matched => segment.push(c),
+            _ => segments.push((matched, c.to_string())),
+        }
+    }
+
+    segments
+        .into_iter()
+        .map(|(matched, segment)| {
+            if matched {
+                html! { <mark class="base-match">{segment}</mark> }
+            } else {
+                html! { segment }
+            }
+        })
+        .collect::<Html>()
+}
+
+/// Finds the next entry that can be chosen, moving forward or backward from
+/// the given index, or from the start or end of the list if no index is
+/// given.
+pub(crate) fn next_enabled(
+    entries: &[ListboxEntry],
+    from: Option<usize>,
+    forward: bool,
+) -> Option<usize> {
+    let enabled = |index: &usize| !entries[*index].disabled;
+
+    match (from, forward) {
+        (None, true) => (0..entries.len()).find(enabled),
+        (None, false) => (0..entries.len()).rev().find(enabled),
+        (Some(from), true) => (from + 1..entries.len()).find(enabled),
+        (Some(from), false) => (0..from.min(entries.len())).rev().find(enabled),
+    }
+}
+
+/// Finds the entry a page away that can be chosen, moving forward or backward
+/// over up to a page of such entries, and stopping at the first or last one.
+fn page_enabled(entries: &[ListboxEntry], from: Option<usize>, forward: bool) -> Option<usize> {
+    let mut index = from;
+
+    for _ in 0..PAGE_ENTRIES {
+        match next_enabled(entries, index, forward) {
+            Some(next) => index = Some(next),
+            None => break,
+        }
+    }
+
+    index
+}
+
+/// Creates a listbox handle, keeping track of the highlighted entry and
+/// scrolling it into view whenever it changes.
+#[hook]
+pub(crate) fn use_listbox() -> UseListboxHandle {
+    let id_state = use_state(new_id);
+    let highlighted = use_state(|| None::<usize>);
+    let type_ahead = use_mut_ref(|| (String::new(), None::<Timeout>));
+    let handle = UseListboxHandle {
+        id: (*id_state).clone(),
+        highlighted,
+        type_ahead,
+    };
+
+    {
+        let handle = handle.clone();
+        let highlighted = handle.highlighted();
+        use_effect_with_deps(
+            move |highlighted| {
+                if let Some(index) = highlighted {
+                    scroll_into_view_nearest(&handle.option_id(*index));
+                }
+            },
+            highlighted,
+        );
+    }
+
+    handle
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(labels: &[(&str, bool)]) -> Vec<ListboxEntry> {
+        labels
+            .iter()
+            .map(|(label, disabled)| ListboxEntry::new(*label, *disabled))
+            .collect()
+    }
+
+    #[test]
+    fn next_enabled_skips_disabled_entries() {
+        let entries = entries(&[
+            ("a", true),
+            ("b", false),
+            ("c", true),
+            ("d", false),
+            ("e", true),
+        ]);
+
+        assert_eq!(next_enabled(&entries, None, true), Some(1));
+        assert_eq!(next_enabled(&entries, None, false), Some(3));
+        assert_eq!(next_enabled(&entries, Some(1), true), Some(3));
+        assert_eq!(next_enabled(&entries, Some(3), false), Some(1));
+        assert_eq!(next_enabled(&entries, Some(3), true), None);
+        assert_eq!(next_enabled(&entries, Some(1), false), None);
+        assert_eq!(next_enabled(&entries, Some(10), false), Some(3));
+        assert_eq!(next_enabled(&[], None, true), None);
+    }
+
+    #[test]
+    fn navigate_arrows_stop_at_ends() {
+        let entries = entries(&[("a", false), ("b", true), ("c", false)]);
+
+        assert_eq!(
+            navigate("ArrowDown", true, Some(0), &entries),
+            Some((Some(2), ListboxKey::Handled))
+        );
+        assert_eq!(
+            navigate("ArrowDown", true, Some(2), &entries),
+            Some((Some(2), ListboxKey::Handled))
+        );
+        assert_eq!(
+            navigate("ArrowUp", true, Some(2), &entries),
+            Some((Some(0), ListboxKey::Handled))
+        );
+        assert_eq!(
+            navigate("ArrowUp", true, Some(0), &entries),
+            Some((Some(0), ListboxKey::Handled))
+        );
+        assert_eq!(
+            navigate("ArrowDown", false, Some(0), &entries),
+            Some((Some(0), ListboxKey::Open))
+        );
+    }
+
+    #[test]
+    fn navigate_home_and_end() {
+        let entries = entries(&[("a", true), ("b", false), ("c", false), ("d", true)]);
+
+        assert_eq!(
+            navigate("Home", true, Some(2), &entries),
+            Some((Some(1), ListboxKey::Handled))
+        );
+        assert_eq!(
+            navigate("End", true, Some(1), &entries),
+            Some((Some(2), ListboxKey::Handled))
+        );
+        assert_eq!(navigate("Home", false, Some(2), &entries), None);
+        assert_eq!(navigate("End", false, Some(1), &entries), None);
+    }
+
+    #[test]
+    fn navigate_page_up_and_page_down() {
+        let entries = (0..25)
+            .map(|index| ListboxEntry::new(index.to_string(), index % 5 == 4))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            navigate("PageDown", true, Some(0), &entries),
+            Some((Some(12), ListboxKey::Handled))
+        );
+        assert_eq!(
+            navigate("PageDown", true, Some(12), &entries),
+            Some((Some(23), ListboxKey::Handled))
+        );
+        assert_eq!(
+            navigate("PageUp", true, Some(12), &entries),
+            Some((Some(0), ListboxKey::Handled))
+        );
+        assert_eq!(
+            navigate("PageDown", true, None, &entries),
+            Some((Some(11), ListboxKey::Handled))
+        );
+        assert_eq!(navigate("PageDown", false, Some(0), &entries), None);
+    }
+
+    #[test]
+    fn navigate_chooses_and_closes() {
+        let entries = entries(&[("a", false), ("b", true)]);
+
+        assert_eq!(
+            navigate("Enter", true, Some(0), &entries),
+            Some((Some(0), ListboxKey::Choose(0)))
+        );
+        assert_eq!(
+            navigate(" ", true, Some(1), &entries),
+            Some((Some(1), ListboxKey::Close))
+        );
+        assert_eq!(
+            navigate("Enter", false, None, &entries),
+            Some((None, ListboxKey::Open))
+        );
+        assert_eq!(
+            navigate("Escape", true, Some(0), &entries),
+            Some((Some(0), ListboxKey::Close))
+        );
+        assert_eq!(navigate("Escape", false, Some(0), &entries), None);
+        assert_eq!(navigate("x", true, Some(0), &entries), None);
+    }
+
+    #[test]
+    fn type_ahead_finds_enabled_prefix_matches() {
+        let entries = entries(&[
+            ("Apple", false),
+            ("Banana", true),
+            ("Blueberry", false),
+            ("Cherry", false),
+        ]);
+
+        assert_eq!(type_ahead_match(&entries, "b", None), Some(2));
+        assert_eq!(type_ahead_match(&entries, "ch", Some(0)), Some(3));
+        assert_eq!(type_ahead_match(&entries, "bl", Some(2)), Some(2));
+        assert_eq!(type_ahead_match(&entries, "z", Some(0)), None);
+        assert_eq!(type_ahead_match(&entries, "", Some(0)), None);
+    }
+
+    #[test]
+    fn type_ahead_cycles_and_wraps() {
+        let entries = entries(&[
+            ("Apple", false),
+            ("Apricot", false),
+            ("Banana", false),
+            ("Avocado", false),
+        ]);
+
+        assert_eq!(type_ahead_match(&entries, "a", None), Some(0));
+        assert_eq!(type_ahead_match(&entries, "aa", Some(0)), Some(1));
+        assert_eq!(type_ahead_match(&entries, "aaa", Some(1)), Some(3));
+        assert_eq!(type_ahead_match(&entries, "aaaa", Some(3)), Some(0));
+        assert_eq!(type_ahead_match(&entries, "av", Some(3)), Some(3));
+        assert_eq!(type_ahead_match(&entries, "b", Some(3)), Some(2));
+    }
+}
@@ -3,14 +3,22 @@ mod badge;
 mod button;
 mod card;
 mod checkbox;
+mod chipmatcher;
 mod chips;
 mod datepicker;
 mod dialog;
 mod error;
+mod errorsummary;
+mod fieldwrapper;
+mod form;
+mod formfield;
 mod icon;
 mod iconbutton;
 mod input;
+mod listbox;
+mod multiselect;
 mod numberinput;
+mod optionsource;
 mod progressbar;
 mod radio;
 mod select;
@@ -19,20 +27,31 @@ mod spinner;
 mod switch;
 mod textarea;
 mod tooltip;
+mod validator;
+mod virtuallist;
+mod wizard;
 
 pub use alert::*;
 pub use badge::*;
 pub use button::*;
 pub use card::*;
 pub use checkbox::*;
+pub use chipmatcher::*;
 pub use chips::*;
 pub use datepicker::*;
 pub use dialog::*;
 pub use error::*;
+pub use errorsummary::*;
+pub use fieldwrapper::*;
+pub use form::*;
+pub use formfield::*;
 pub use icon::*;
 pub use iconbutton::*;
 pub use input::*;
+pub(crate) use listbox::*;
+pub use multiselect::*;
 pub use numberinput::*;
+pub use optionsource::*;
 pub use progressbar::*;
 pub use radio::*;
 pub use select::*;
@@ -41,3 +60,6 @@ pub use spinner::*;
 pub use switch::*;
 pub use textarea::*;
 pub use tooltip::*;
+pub use validator::*;
+pub use virtuallist::*;
+pub use wizard::*;
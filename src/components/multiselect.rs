@@ -0,0 +1,647 @@
+use super::*;
+use crate::util::*;
+use std::collections::HashMap;
+use yew::prelude::*;
+use yew_hooks::use_click_away;
+
+/// Orders a selection according to the order of the options, removing
+/// duplicates and anything that is not an option.
+fn ordered_selection<T: SelectItem + PartialEq + Clone>(options: &[T], selected: &[T]) -> Vec<T> {
+    // The selection is looked up by label, so that only options sharing a
+    // label need to be compared.
+    let mut selected_by_label = HashMap::<String, Vec<&T>>::new();
+
+    for option in selected {
+        selected_by_label
+            .entry(option.label())
+            .or_default()
+            .push(option);
+    }
+
+    options
+        .iter()
+        .filter(|option| {
+            selected_by_label
+                .get(&option.label())
+                .is_some_and(|selected| selected.contains(option))
+        })
+        .cloned()
+        .collect()
+}
+
+/// Summarizes a selection for display in the multi-select button.
+fn multi_select_summary<T: SelectItem>(selected: &[T], limit: usize, placeholder: &str) -> String {
+    match selected.len() {
+        0 => placeholder.to_owned(),
+        len if len <= limit => selected
+            .iter()
+            .map(SelectItem::label)
+            .collect::<Vec<_>>()
+            .join(", "),
+        len => format!("{len} selected"),
+    }
+}
+
+/// Multi-select properties.
+#[derive(Properties, PartialEq, Clone)]
+pub struct MultiSelectProps<T: SelectItem + PartialEq + Clone + 'static> {
+    /// The state of the selected options. This is treated as a set, and is
+    /// kept in the same order as the options.
+    #[prop_or_default]
+    pub state: Option<UseStateHandle<Vec<T>>>,
+    /// The selected options. Used along with `on_change` in place of `state`.
+    #[prop_or_default]
+    pub value: Option<Vec<T>>,
+    /// The callback called when the selected options change.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_change: Callback<Vec<T>>,
+    /// The callback called when the selection loses focus.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_blur: Callback<()>,
+    /// The name of the field within a surrounding form.
+    #[prop_or_default]
+    pub name: String,
+    /// The ungrouped options to choose from.
+    #[prop_or_default]
+    pub options: Vec<T>,
+    /// Groups of options to choose from, displayed after the ungrouped
+    /// options. Grouped options follow the ungrouped options when counting
+    /// option indices.
+    #[prop_or_default]
+    pub groups: Vec<SelectOptionGroup<T>>,
+    /// The selection label.
+    #[prop_or_default]
+    pub label: String,
+    /// The text displayed when nothing is selected.
+    #[prop_or("Select...".to_owned())]
+    pub placeholder: String,
+    /// The maximum number of selected option labels to display before
+    /// displaying the number of selected options instead.
+    #[prop_or(2)]
+    pub summary_limit: usize,
+    /// The label of the button selecting every option.
+    #[prop_or("Select all".to_owned())]
+    pub select_all_label: String,
+    /// The label of the button clearing the selection.
+    #[prop_or("Clear".to_owned())]
+    pub clear_label: String,
+    /// Whether at least one selection is required.
+    #[prop_or(false)]
+    pub required: bool,
+    /// Whether to mark the selection as optional when it is not required.
+    #[prop_or(false)]
+    pub show_optional: bool,
+    /// Help text describing the selection.
+    #[prop_or_default]
+    pub help: String,
+    /// An optional error message.
+    #[prop_or_default]
+    pub error: Option<String>,
+    /// Whether the selection is disabled.
+    #[prop_or(false)]
+    pub disabled: bool,
+    /// Whether the options can be narrowed down with a search field in the
+    /// dropdown.
+    #[prop_or(false)]
+    pub filterable: bool,
+    /// Search field placeholder text.
+    #[prop_or("Search...".to_owned())]
+    pub search_placeholder: String,
+    /// The heights of the rows in the dropdown. Only the rows scrolled into
+    /// view are rendered if this is set, keeping long lists of options
+    /// responsive.
+    #[prop_or_default]
+    pub row_height: Option<RowHeight>,
+    /// Whether every selected option can be deselected at once with an
+    /// inline clear button or the Backspace key. Disabled options keep their
+    /// current selection state.
+    #[prop_or(false)]
+    pub clearable: bool,
+    /// The callback called when the selection is cleared.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_clear: Callback<()>,
+}
+
+/// A select component allowing any number of options to be chosen.
+#[function_component]
+pub fn MultiSelect<T: SelectItem + PartialEq + Clone + 'static>(
+    props: &MultiSelectProps<T>,
+) -> Html {
+    let MultiSelectProps {
+        state,
+        value,
+        on_change,
+        on_blur,
+        name,
+        options,
+        groups,
+        label,
+        placeholder,
+        summary_limit,
+        select_all_label,
+        clear_label,
+        required,
+        show_optional,
+        help,
+        error,
+        disabled,
+        filterable,
+        search_placeholder,
+        row_height,
+        clearable,
+        on_clear,
+    } = props.clone();
+
+    let state = use_value_handle(state, value, on_change);
+
+    let id_state = use_state(new_id);
+    let id = (*id_state).clone();
+    let dropdown_open = use_state(|| false);
+    let open = *dropdown_open;
+    let query_state = use_state(String::new);
+    let query = if filterable {
+        (*query_state).clone()
+    } else {
+        String::new()
+    };
+    let search_node = use_node_ref();
+
+    // Matching every option against the query is costly for long lists, so
+    // the rows are only rebuilt when the options or the query change, and the
+    // selection is only ordered when the options or the selection change.
+    let selection_deps = (options.clone(), groups.clone(), (*state).clone());
+    let dropdown = use_memo(
+        |(options, groups, query)| {
+            let (options, sections) = select_sections(options, groups);
+            let rows = select_rows(&options, &sections, query, false);
+            let entries = rows
+                .iter()
+                .map(|row| select_row_entry(&options, "", row))
+                .collect();
+
+            SelectDropdown {
+                options,
+                sections,
+                rows,
+                entries,
+            }
+        },
+        (options, groups, query.clone()),
+    );
+    let SelectDropdown { options, rows, .. } = &*dropdown;
+    let selected = use_memo(
+        |(_, _, selected)| ordered_selection(options, selected),
+        selection_deps,
+    );
+    let selection = selected.iter().map(SelectItem::label).collect::<Vec<_>>();
+    let button_node = use_node_ref();
+    let form_error = use_form_registration(
+        &name,
+        FormField {
+            label: label.clone(),
+            node: button_node.clone(),
+            ..FormField::new(FormValue::Choices(selection), required, error.clone())
+        },
+    );
+    use_form_restore(&name, {
+        let state = state.clone();
+        let dropdown = dropdown.clone();
+        move |value| {
+            if let FormValue::Choices(labels) = value {
+                state.set(
+                    dropdown
+                        .options
+                        .iter()
+                        .filter(|option| labels.contains(&option.label()))
+                        .cloned()
+                        .collect(),
+                );
+            }
+        }
+    });
+    let error = error.or(form_error);
+
+    let listbox = use_listbox();
+    let virtual_list = use_virtual_list(rows.len(), row_height, listbox.highlighted());
+
+    use_effect_with_deps(
+        {
+            let search_node = search_node.clone();
+            move |open: &bool| {
+                if *open && filterable {
+                    focus_element(&search_node);
+                }
+            }
+        },
+        open,
+    );
+
+    let set_selected = {
+        let state = state.clone();
+        let dropdown = dropdown.clone();
+        move |new_selected: Vec<T>| {
+            state.set(ordered_selection(&dropdown.options, &new_selected));
+        }
+    };
+    let toggle = {
+        let dropdown = dropdown.clone();
+        let selected = selected.clone();
+        let set_selected = set_selected.clone();
+        move |row_index: usize| {
+            if let Some(SelectRow::Option { index, .. }) = dropdown.rows.get(row_index) {
+                let option = &dropdown.options[*index];
+
+                if !option.disabled() {
+                    let mut new_selected = (*selected).clone();
+
+                    if new_selected.contains(option) {
+                        new_selected.retain(|selected_option| selected_option != option);
+                    } else {
+                        new_selected.push(option.clone());
+                    }
+
+                    set_selected(new_selected);
+                }
+            }
+        }
+    };
+    let close = {
+        let dropdown_open = dropdown_open.clone();
+        let query_state = query_state.clone();
+        let button_node = button_node.clone();
+        move |refocus: bool| {
+            dropdown_open.set(false);
+            query_state.set(String::new());
+
+            if refocus && filterable {
+                focus_element(&button_node);
+            }
+        }
+    };
+
+    // The options matching the query, which the header buttons act on.
+    let visible_options = {
+        let dropdown = dropdown.clone();
+        move || {
+            dropdown
+                .rows
+                .iter()
+                .filter_map(|row| row.option_index().map(|index| &dropdown.options[index]))
+                .cloned()
+                .collect::<Vec<_>>()
+        }
+    };
+    // Disabled options keep their current selection state.
+    let on_select_all_click = {
+        let selected = selected.clone();
+        let visible_options = visible_options.clone();
+        let set_selected = set_selected.clone();
+        move |_| {
+            let mut new_selected = (*selected).clone();
+            new_selected.extend(
+                visible_options()
+                    .into_iter()
+                    .filter(|option| !option.disabled()),
+            );
+            set_selected(new_selected);
+        }
+    };
+    let on_clear_click = {
+        let selected = selected.clone();
+        let set_selected = set_selected.clone();
+        move |_| {
+            let visible_options = visible_options();
+            let new_selected = selected
+                .iter()
+                .filter(|option| option.disabled() || !visible_options.contains(option))
+                .cloned()
+                .collect();
+            set_selected(new_selected);
+        }
+    };
+
+    let can_clear = clearable && selected.iter().any(|option| !option.disabled());
+    let clear = {
+        let selected = selected.clone();
+        let button_node = button_node.clone();
+        Callback::from(move |_| {
+            set_selected(
+                selected
+                    .iter()
+                    .filter(|option| option.disabled())
+                    .cloned()
+                    .collect(),
+            );
+            focus_element(&button_node);
+            on_clear.emit(());
+        })
+    };
+
+    let on_button_click = {
+        let dropdown_open = dropdown_open.clone();
+        let listbox = listbox.clone();
+        let dropdown = dropdown.clone();
+        let close = close.clone();
+        move |_| {
+            if !disabled {
+                if open {
+                    close(false);
+                } else {
+                    listbox.open_at(None, &dropdown.entries);
+                    dropdown_open.set(true);
+                }
+            }
+        }
+    };
+    let onkeydown = {
+        let dropdown_open = dropdown_open.clone();
+        let listbox = listbox.clone();
+        let dropdown = dropdown.clone();
+        let toggle = toggle.clone();
+        let close = close.clone();
+        let clear = clear.clone();
+        move |event: KeyboardEvent| {
+            let entries = &dropdown.entries;
+
+            if disabled {
+                return;
+            }
+
+            if can_clear && !open && event.key() == "Backspace" {
+                event.prevent_default();
+                clear.emit(());
+                return;
+            }
+
+            // Tab moves focus into the open dropdown, so that its header
+            // buttons can be reached. The dropdown closes once focus leaves.
+            if open && event.key() == "Tab" {
+                return;
+            }
+
+            match listbox.handle_key(&event, open, entries) {
+                ListboxKey::Open => {
+                    listbox.open_at(None, entries);
+                    dropdown_open.set(true);
+                }
+                ListboxKey::Close => close(false),
+                // Type-ahead only moves the highlight while the dropdown is
+                // closed, rather than changing the selection.
+                ListboxKey::Choose(index) if open => toggle(index),
+                ListboxKey::Choose(_) | ListboxKey::Handled | ListboxKey::Ignored => {}
+            }
+        }
+    };
+
+    let on_search_input = {
+        let query_state = query_state.clone();
+        let listbox = listbox.clone();
+        let dropdown = dropdown.clone();
+        move |event: InputEvent| {
+            let new_query = input_event_value(event);
+            let SelectDropdown {
+                options, sections, ..
+            } = &*dropdown;
+            let new_entries = select_rows(options, sections, &new_query, false)
+                .iter()
+                .map(|row| select_row_entry(options, "", row))
+                .collect::<Vec<_>>();
+            listbox.highlight(next_enabled(&new_entries, None, true));
+            query_state.set(new_query);
+        }
+    };
+    let on_search_keydown = {
+        let listbox = listbox.clone();
+        let toggle = toggle.clone();
+        let close = close.clone();
+        let dropdown = dropdown.clone();
+        move |event: KeyboardEvent| {
+            if event.key() == "Tab" {
+                return;
+            }
+
+            match listbox.handle_search_key(&event, &dropdown.entries) {
+                ListboxKey::Choose(index) => toggle(index),
+                ListboxKey::Close => close(event.key() == "Escape"),
+                ListboxKey::Open | ListboxKey::Handled | ListboxKey::Ignored => {}
+            }
+        }
+    };
+    let on_header_keydown = {
+        let close = close.clone();
+        let button_node = button_node.clone();
+        move |event: KeyboardEvent| {
+            if event.key() == "Escape" {
+                event.prevent_default();
+                close(false);
+                focus_element(&button_node);
+            }
+        }
+    };
+
+    let select_node = use_node_ref();
+    use_click_away(select_node.clone(), {
+        let close = close.clone();
+        move |_| {
+            if open {
+                close(false);
+            }
+        }
+    });
+    let onfocusout = {
+        let select_node_local = select_node.clone();
+        let close = close.clone();
+        move |event: FocusEvent| {
+            if focus_leaving(&event, &select_node_local) {
+                if open {
+                    close(false);
+                }
+
+                on_blur.emit(());
+            }
+        }
+    };
+
+    let on_popup_scroll = {
+        let virtual_list = virtual_list.clone();
+        move |_| {
+            virtual_list.on_scroll();
+        }
+    };
+
+    let summary = multi_select_summary(&selected, summary_limit, &placeholder);
+
+    let row_range = virtual_list.range();
+    let row_list = rows
+        .iter()
+        .enumerate()
+        .skip(row_range.start)
+        .take(row_range.len())
+        .map(|(row_index, row)| {
+            let (index, matches) = match row {
+                SelectRow::Option { index, matches } => (*index, matches),
+                row => return select_divider_html(row),
+            };
+            let option = &options[index];
+            let option_disabled = option.disabled();
+            let checked = selected.contains(option);
+            let highlighted = listbox.highlighted() == Some(row_index);
+            let onclick = {
+                let toggle = toggle.clone();
+                move |_| {
+                    toggle(row_index);
+                }
+            };
+            let onmouseenter = {
+                let listbox = listbox.clone();
+                move |_| {
+                    if !option_disabled {
+                        listbox.highlight(Some(row_index));
+                    }
+                }
+            };
+
+            html! {
+                <div
+                    id={listbox.option_id(row_index)}
+                    role="option"
+                    aria-selected={checked.to_string()}
+                    aria-disabled={option_disabled.to_string()}
+                    {onclick}
+                    {onmouseenter}
+                    class={classes!(
+                        "base-select-option",
+                        "base-multi-select-option",
+                        option_disabled.then_some("base-select-option-disabled"),
+                        checked.then_some("base-select-option-selected"),
+                        highlighted.then_some("base-select-option-highlighted"),
+                    )}
+                >
+                    <div class={classes!("base-checkbox", option_disabled.then_some("base-checkbox-disabled"))}>
+                        <input
+                            type="checkbox"
+                            {checked}
+                            disabled={option_disabled}
+                            tabindex="-1"
+                            aria-hidden="true"
+                            class="base-checkbox-input"
+                        />
+                        <span class="base-checkmark">
+                            <img src="assets/svg/check-solid.svg" class="base-checkmark-icon" />
+                        </span>
+                        <span class="base-checkbox-label">
+                            {highlighted_label(&option.label(), matches)}
+                        </span>
+                    </div>
+                </div>
+            }
+        })
+        .collect::<Html>();
+
+    html! {
+        <FieldWrapper
+            id={id.clone()}
+            {label}
+            {required}
+            {show_optional}
+            {help}
+            error={error.clone()}
+            {disabled}
+            class={classes!("base-select-container", "base-multi-select-container", disabled.then_some("base-select-container-disabled"), open.then_some("base-select-container-open"))}
+            label_class="base-select-label"
+        >
+            <div ref={select_node} {onfocusout} class="base-select">
+                <button
+                    ref={button_node}
+                    type="button"
+                    {id}
+                    onclick={on_button_click}
+                    {onkeydown}
+                    {disabled}
+                    role="combobox"
+                    aria-haspopup="listbox"
+                    aria-expanded={open.to_string()}
+                    aria-controls={listbox.id()}
+                    aria-activedescendant={(!filterable).then(|| listbox.active_descendant(open)).flatten()}
+                    class={classes!("base-select-button", error.clone().map(|_| "base-select-button-invalid"))}
+                >
+                    <div class="base-select-button-selection">
+                        {summary}
+                    </div>
+                    <Icon name="angle-down-solid" {disabled} class="base-select-button-icon" />
+                </button>
+                {clear_button_html(can_clear, disabled, clear)}
+                <div class="base-select-dropdown">
+                    {filterable.then_some(html! {
+                        <div class="base-select-search">
+                            <input
+                                ref={search_node}
+                                type="text"
+                                value={query}
+                                placeholder={search_placeholder}
+                                oninput={on_search_input}
+                                onkeydown={on_search_keydown}
+                                role="combobox"
+                                aria-autocomplete="list"
+                                aria-expanded={open.to_string()}
+                                aria-controls={listbox.id()}
+                                aria-activedescendant={listbox.active_descendant(open)}
+                                class="base-select-search-input"
+                            />
+                        </div>
+                    })}
+                    <div
+                        onmousedown={|event: MouseEvent| event.prevent_default()}
+                        onkeydown={on_header_keydown}
+                        class="base-multi-select-header"
+                    >
+                        <button
+                            type="button"
+                            onclick={on_select_all_click}
+                            class="base-multi-select-header-button"
+                        >
+                            {select_all_label}
+                        </button>
+                        <button
+                            type="button"
+                            onclick={on_clear_click}
+                            class="base-multi-select-header-button"
+                        >
+                            {clear_label}
+                        </button>
+                    </div>
+                    <div
+                        id={listbox.id()}
+                        role="listbox"
+                        aria-multiselectable="true"
+                        ref={virtual_list.viewport()}
+                        onmousedown={|event: MouseEvent| event.prevent_default()}
+                        onscroll={on_popup_scroll}
+                        class="base-select-popup"
+                    >
+                        {virtual_list.render(row_list)}
+                        {dropdown_status_html("base-select", false, None, rows.is_empty())}
+                    </div>
+                </div>
+            </div>
+        </FieldWrapper>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ordered_selection_follows_options() {
+        let options = ["a", "b", "c", "d"];
+
+        assert_eq!(ordered_selection(&options, &["c", "a"]), vec!["a", "c"]);
+        assert_eq!(
+            ordered_selection(&options, &["d", "d", "b"]),
+            vec!["b", "d"]
+        );
+        assert_eq!(ordered_selection(&options, &["e", "a"]), vec!["a"]);
+        assert_eq!(ordered_selection(&options, &[]), Vec::<&str>::new());
+    }
+}
@@ -1,7 +1,32 @@
 use super::*;
 use crate::util::*;
+use gloo_timers::callback::{Interval, Timeout};
+use std::fmt::{self, Display, Formatter};
+use yew::platform::spawn_local;
 use yew::prelude::*;
 
+/// The time in milliseconds a stepper button must be held before stepping
+/// repeats.
+const REPEAT_DELAY_MS: u32 = 400;
+
+/// The time in milliseconds between checks for repeating a step while a
+/// stepper button is held.
+const REPEAT_TICK_MS: u32 = 50;
+
+/// The number of steps in a page step, when no page step is given.
+const PAGE_STEPS: usize = 10;
+
+/// Gets the number of repeat ticks to wait before the next step, given the
+/// number of steps repeated so far, so that holding a stepper button
+/// accelerates.
+fn repeat_ticks(repeats: usize) -> usize {
+    match repeats {
+        0..=4 => 4,
+        5..=14 => 2,
+        _ => 1,
+    }
+}
+
 /// Shortens a number to a specified number of decimal places.
 fn shorten_to(value_str: &str, decimals: u16) -> String {
     match value_str.find('.') {
@@ -43,22 +68,52 @@ fn transform_number(value_str: &str, decimals: u16) -> String {
     value_str
 }
 
+/// Clamps a number to the allowed range, along with whether it was out of
+/// range.
+fn clamp_number<N: Number>(value: N, min: N, max: N) -> (N, bool) {
+    if value < min {
+        (min, true)
+    } else if value > max {
+        (max, true)
+    } else {
+        (value, false)
+    }
+}
+
 /// Parses the value of a string representation of a number in a text input box.
 fn parse_number_value<N: Number>(value_str: &str, min: N, max: N) -> Option<(N, bool)> {
     match value_str.parse::<N>() {
-        Ok(value) => {
-            if value < min {
-                Some((min, true))
-            } else if value > max {
-                Some((max, true))
-            } else {
-                Some((value, false))
-            }
-        }
+        Ok(value) => Some(clamp_number(value, min, max)),
         Err(_) => None,
     }
 }
 
+/// Steps a number up or down by an amount, saturating at the bounds of the
+/// allowed range rather than overflowing.
+fn step_number<N: Number>(value: N, amount: N, up: bool, min: N, max: N) -> N {
+    let (value, _) = clamp_number(value, min, max);
+
+    if up {
+        value
+            .checked_add(amount)
+            .filter(|value| *value <= max)
+            .unwrap_or(max)
+    } else {
+        value
+            .checked_sub(amount)
+            .filter(|value| *value >= min)
+            .unwrap_or(min)
+    }
+}
+
+/// Gets the default page step of ten steps, saturating at the largest number
+/// rather than overflowing.
+fn default_page_step<N: Number>(step: N) -> N {
+    (0..PAGE_STEPS)
+        .try_fold(N::default(), |total, _| total.checked_add(step))
+        .unwrap_or(N::NUMBER_MAX)
+}
+
 /// Parses a string representation of a number in a text input box.
 fn parse_number<N: Number>(value_str: &str, min: N, max: N) -> Option<(N, bool)> {
     if value_str.is_empty() {
@@ -99,6 +154,12 @@ impl<N: Number> NumberState<N> {
         parse_number(&self.state, self.min, self.max).unwrap().0
     }
 
+    /// Checks whether the inner state represents the given value.
+    pub fn represents(&self, value: N) -> bool {
+        parse_number(&self.state, self.min, self.max).map(|(parsed, _)| parsed)
+            == Some(clamp_number(value, self.min, self.max).0)
+    }
+
     /// Sets the inner state.
     pub fn set(&mut self, new_value_str: &str) {
         let new_value_transformed = transform_number(new_value_str, self.decimals);
@@ -114,12 +175,12 @@ impl<N: Number> NumberState<N> {
     }
 }
 
-impl<N: Number> ToString for NumberState<N> {
-    fn to_string(&self) -> String {
+impl<N: Number> Display for NumberState<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         if self.state.is_empty() {
-            N::default().to_string()
+            write!(f, "{}", N::default())
         } else {
-            self.state.clone()
+            write!(f, "{}", self.state)
         }
     }
 }
@@ -135,11 +196,36 @@ impl<N: Number> Default for NumberState<N> {
     }
 }
 
+/// Gets the number state to display in place of the current one, if it no
+/// longer represents the value, as after a form field is reset.
+fn resynced_number_state<N: Number>(
+    display: &NumberState<N>,
+    value: N,
+    min: N,
+    max: N,
+    decimals: u16,
+) -> Option<NumberState<N>> {
+    (!display.represents(value)).then(|| NumberState::new(value, min, max, decimals))
+}
+
 /// Input properties.
 #[derive(Properties, PartialEq, Clone)]
 pub struct NumberInputProps<N: Number> {
     /// The number input state.
-    pub state: UseStateHandle<N>,
+    #[prop_or_default]
+    pub state: Option<UseStateHandle<N>>,
+    /// The number input value. Used along with `on_change` in place of `state`.
+    #[prop_or_default]
+    pub value: Option<N>,
+    /// The callback called when the number input value changes.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_change: Callback<N>,
+    /// The callback called when the number input loses focus.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_blur: Callback<()>,
+    /// The name of the field within a surrounding form.
+    #[prop_or_default]
+    pub name: String,
     /// The number input label.
     #[prop_or_default]
     pub label: String,
@@ -155,15 +241,55 @@ pub struct NumberInputProps<N: Number> {
     /// The maximum number of decimal places.
     #[prop_or(u16::MAX)]
     pub decimals: u16,
+    /// The amount the value changes by when stepped with the arrow keys or
+    /// the stepper buttons.
+    #[prop_or(N::NUMBER_STEP)]
+    pub step: N,
+    /// The amount the value changes by when stepped with the Page Up and
+    /// Page Down keys. Defaults to ten steps.
+    #[prop_or_default]
+    pub page_step: Option<N>,
+    /// Whether to display buttons stepping the value up and down. Holding a
+    /// button repeats the step, speeding up the longer it is held.
+    #[prop_or(false)]
+    pub stepper: bool,
     /// Whether the input is required to be filled out.
     #[prop_or(false)]
     pub required: bool,
+    /// Whether to mark the number input as optional when it is not required.
+    #[prop_or(false)]
+    pub show_optional: bool,
+    /// Help text describing the number input.
+    #[prop_or_default]
+    pub help: String,
     /// An optional error message.
     #[prop_or_default]
     pub error: Option<String>,
+    /// Validators applied to the number input value.
+    #[prop_or_default]
+    pub validators: Validators<N>,
+    /// When validation errors are displayed.
+    #[prop_or_default]
+    pub validation_mode: ValidationMode,
+    /// Asynchronous validators applied to the number input value. These are only
+    /// run once all other validation passes.
+    #[prop_or_default]
+    pub async_validators: AsyncValidators<N>,
+    /// The time in milliseconds to wait after the value changes before
+    /// running asynchronous validators.
+    #[prop_or(300)]
+    pub async_debounce_ms: u32,
     /// Whether the input is disabled.
     #[prop_or(false)]
     pub disabled: bool,
+    /// Whether the input can be cleared with an inline clear button or the
+    /// Escape key. Clearing resets the value to zero, or to the closest
+    /// allowed value if zero is out of range.
+    #[prop_or(false)]
+    pub clearable: bool,
+    /// The callback called when the input is cleared.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_clear: Callback<()>,
 }
 
 /// An input element.
@@ -171,16 +297,33 @@ pub struct NumberInputProps<N: Number> {
 pub fn NumberInput<N: Number + 'static>(props: &NumberInputProps<N>) -> Html {
     let NumberInputProps {
         state,
+        value,
+        on_change,
+        on_blur,
+        name,
         label,
         placeholder,
         min,
         max,
         decimals,
+        step,
+        page_step,
+        stepper,
         required,
+        show_optional,
+        help,
         error,
+        validators,
+        validation_mode,
+        async_validators,
+        async_debounce_ms,
         disabled,
+        clearable,
+        on_clear,
     } = props.clone();
 
+    let state = use_value_handle(state, value, on_change);
+
     let state_update = use_state(|| Option::<(N, NumberState<N>)>::None);
     let number_state = use_state(|| NumberState::new(*state, min, max, decimals));
 
@@ -190,38 +333,429 @@ pub fn NumberInput<N: Number + 'static>(props: &NumberInputProps<N>) -> Html {
         state_update.set(None);
     }
 
+    // Keep the displayed text in line with a value set from outside the
+    // input, such as a controlled `value` or a form reset.
+    {
+        let number_state = number_state.clone();
+        use_effect_with_deps(
+            move |value| {
+                if let Some(new_number_state) =
+                    resynced_number_state(&number_state, *value, min, max, decimals)
+                {
+                    number_state.set(new_number_state);
+                }
+                || ()
+            },
+            *state,
+        );
+    }
+
+    let input_node = use_node_ref();
+    let validation = use_validation(validation_mode);
+    let validation_error = validators.validate(&state);
+    let async_validation = use_async_validation(
+        *state,
+        validation_error.is_none() && !async_validators.is_empty(),
+        async_debounce_ms,
+        move |value: &N| async_validators.validate(value),
+    );
+    let validation_error = validation_error.or(async_validation.error);
+    let pending = async_validation.pending;
+    let form_error = use_form_registration(
+        &name,
+        FormField {
+            label: label.clone(),
+            node: input_node.clone(),
+            pending,
+            error_shown: error.is_some() || validation.visible(),
+            ..FormField::new(
+                FormValue::Number(state.as_f64()),
+                required,
+                error.clone().or_else(|| validation_error.clone()),
+            )
+        },
+    );
+    use_form_restore(&name, {
+        let state_update = state_update.clone();
+        move |value| {
+            if let FormValue::Number(value) = value {
+                if let Ok(value) = value.to_string().parse::<N>() {
+                    state_update.set(Some((value, NumberState::new(value, min, max, decimals))));
+                }
+            }
+        }
+    });
+    let error = error
+        .or_else(|| validation.visible_error(validation_error))
+        .or(form_error);
     let value_str = (*number_state).to_string();
     let id_state = use_state(new_id);
     let id = (*id_state).clone();
 
-    let oninput = move |event: InputEvent| {
-        let new_value_str = input_event_value(event);
-        let mut new_number_state = (*number_state).clone();
-        new_number_state.set(&new_value_str);
-        let new_state = new_number_state.get();
-        let new_state_str = NumberState::<N>::default();
+    let cleared_value = parse_number_value(&N::default().to_string(), min, max)
+        .map(|(value, _)| value)
+        .unwrap_or(min);
+    let can_clear = clearable && *state != cleared_value;
+    let clear = {
+        let state_update = state_update.clone();
+        let validation = validation.clone();
+        let input_node = input_node.clone();
+        Callback::from(move |_| {
+            state_update.set(Some((
+                cleared_value,
+                NumberState::new(cleared_value, min, max, decimals),
+            )));
+            validation.changed();
+            focus_element(&input_node);
+            on_clear.emit(());
+        })
+    };
+
+    // The latest value, kept up to date between renders so that repeated
+    // steps build on one another.
+    let latest = use_mut_ref(|| *state);
+    *latest.borrow_mut() = *state;
+    let set_value = {
+        let state_update = state_update.clone();
+        let validation = validation.clone();
+        let latest = latest.clone();
+        move |new_value: N| {
+            let mut new_number_state = NumberState::new(new_value, min, max, decimals);
+            new_number_state.set(&new_value.to_string());
+            let new_value = new_number_state.get();
 
-        number_state.set(new_state_str);
-        state_update.set(Some((new_state, new_number_state)));
+            if new_value != *latest.borrow() {
+                *latest.borrow_mut() = new_value;
+                state_update.set(Some((new_value, new_number_state)));
+                validation.changed();
+            }
+        }
+    };
+    // Returns whether the value can be stepped further in the same
+    // direction.
+    let step_by = {
+        let set_value = set_value.clone();
+        let latest = latest.clone();
+        move |amount: N, up: bool| {
+            let current = *latest.borrow();
+            let new_value = step_number(current, amount, up, min, max);
+            set_value(new_value);
+            new_value != if up { max } else { min }
+        }
+    };
+    let (clamped, _) = clamp_number(*state, min, max);
+    let can_step_up = !disabled && clamped < max;
+    let can_step_down = !disabled && clamped > min;
+
+    let onkeydown = {
+        let clear = clear.clone();
+        let set_value = set_value.clone();
+        let step_by = step_by.clone();
+        move |event: KeyboardEvent| {
+            match event.key().as_str() {
+                "Escape" if can_clear => clear.emit(()),
+                "ArrowUp" => {
+                    step_by(step, true);
+                }
+                "ArrowDown" => {
+                    step_by(step, false);
+                }
+                "PageUp" => {
+                    step_by(page_step.unwrap_or_else(|| default_page_step(step)), true);
+                }
+                "PageDown" => {
+                    step_by(page_step.unwrap_or_else(|| default_page_step(step)), false);
+                }
+                "Home" => set_value(min),
+                "End" => set_value(max),
+                _ => return,
+            }
+
+            event.prevent_default();
+        }
+    };
+
+    // The pending and repeating timers while a stepper button is held, and
+    // whether the button was pressed with a pointer, in which case the click
+    // that follows has already been handled.
+    let repeat = use_mut_ref(|| (None::<Timeout>, None::<Interval>, false));
+    {
+        let repeat = repeat.clone();
+        use_effect_with_deps(
+            move |_| {
+                move || {
+                    *repeat.borrow_mut() = (None, None, false); // timers are cancelled when dropped
+                }
+            },
+            (),
+        );
+    }
+    let stepper_button = |up: bool| {
+        let enabled = if up { can_step_up } else { can_step_down };
+        let on_pointer_down = {
+            let repeat = repeat.clone();
+            let step_by = step_by.clone();
+            move |event: PointerEvent| {
+                if !enabled || event.button() != 0 {
+                    return;
+                }
+
+                if !step_by(step, up) {
+                    *repeat.borrow_mut() = (None, None, true);
+                    return;
+                }
+
+                let step_by = step_by.clone();
+                let repeat_local = repeat.clone();
+                *repeat.borrow_mut() = (
+                    Some(Timeout::new(REPEAT_DELAY_MS, move || {
+                        let mut ticks = 0;
+                        let mut repeats = 0;
+                        let repeat = repeat_local.clone();
+                        let interval = Interval::new(REPEAT_TICK_MS, move || {
+                            ticks += 1;
+
+                            if ticks >= repeat_ticks(repeats) {
+                                ticks = 0;
+                                repeats += 1;
+
+                                // Repeating stops once the bound is reached.
+                                // The interval is dropped after its callback
+                                // returns, as it cannot be dropped while its
+                                // callback is running.
+                                if !step_by(step, up) {
+                                    let repeat = repeat.clone();
+                                    spawn_local(async move {
+                                        repeat.borrow_mut().1 = None;
+                                    });
+                                }
+                            }
+                        });
+
+                        repeat_local.borrow_mut().1 = Some(interval);
+                    })),
+                    None,
+                    true,
+                );
+            }
+        };
+        let on_release = {
+            let repeat = repeat.clone();
+            move |_: PointerEvent| {
+                let mut repeat = repeat.borrow_mut();
+                repeat.0 = None;
+                repeat.1 = None;
+            }
+        };
+        // A pointer leaving the button before it is released produces no
+        // click.
+        let on_leave = {
+            let repeat = repeat.clone();
+            move |_: PointerEvent| {
+                *repeat.borrow_mut() = (None, None, false);
+            }
+        };
+        // Clicks not preceded by a pointer press come from the keyboard.
+        let on_click = {
+            let repeat = repeat.clone();
+            let step_by = step_by.clone();
+            Callback::from(move |_| {
+                let pressed = std::mem::take(&mut repeat.borrow_mut().2);
+
+                if !pressed {
+                    step_by(step, up);
+                }
+            })
+        };
+        let (name, label, class) = if up {
+            ("angle-up-solid", "Increment", "base-number-input-increment")
+        } else {
+            (
+                "angle-down-solid",
+                "Decrement",
+                "base-number-input-decrement",
+            )
+        };
+
+        html! {
+            <div
+                onpointerdown={on_pointer_down}
+                onpointerup={on_release.clone()}
+                onpointercancel={on_release}
+                onpointerleave={on_leave}
+                class={class}
+            >
+                <IconButton
+                    {name}
+                    size={IconButtonSize::Small}
+                    {label}
+                    disabled={!enabled}
+                    {on_click}
+                />
+            </div>
+        }
+    };
+    let stepper_buttons = stepper.then(|| {
+        html! {
+            <div class="base-number-input-stepper">
+                {stepper_button(true)}
+                {stepper_button(false)}
+            </div>
+        }
+    });
+
+    let oninput = {
+        let validation = validation.clone();
+        move |event: InputEvent| {
+            let new_value_str = input_event_value(event);
+            let mut new_number_state = (*number_state).clone();
+            new_number_state.set(&new_value_str);
+            let new_state = new_number_state.get();
+            let new_state_str = NumberState::<N>::default();
+
+            number_state.set(new_state_str);
+            state_update.set(Some((new_state, new_number_state)));
+            validation.changed();
+        }
+    };
+    let onblur = move |_| {
+        validation.blurred();
+        on_blur.emit(());
     };
 
     html! {
-        <div class={classes!("base-input-container", disabled.then_some("base-input-container-disabled"))}>
-            <label for={id.clone()} class="base-input-label">
-                {label}
-                <span class="base-required-mark">{required.then_some(" *").unwrap_or_default()}</span>
-            </label>
+        <FieldWrapper
+            id={id.clone()}
+            {label}
+            {required}
+            {show_optional}
+            {help}
+            {pending}
+            error={error.clone()}
+            {disabled}
+            class={classes!("base-input-container", disabled.then_some("base-input-container-disabled"))}
+            label_class="base-input-label"
+        >
             <input
+                ref={input_node}
                 type="text"
                 value={value_str}
                 {id}
+                {name}
                 {oninput}
+                {onkeydown}
+                {onblur}
                 {placeholder}
                 {required}
                 {disabled}
+                role="spinbutton"
+                aria-valuemin={min.to_string()}
+                aria-valuemax={max.to_string()}
+                aria-valuenow={state.to_string()}
                 class={classes!("base-input", error.clone().map(|_| "base-input-invalid"))}
             />
-            <Error message={error} size={ErrorSize::Small} />
-        </div>
+            {clear_button_html(can_clear, disabled, clear)}
+            {stepper_buttons}
+        </FieldWrapper>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clamp_number_bounds() {
+        assert_eq!(clamp_number(i8::MIN, -10, 10), (-10, true));
+        assert_eq!(clamp_number(i8::MAX, -10, 10), (10, true));
+        assert_eq!(clamp_number(-10i8, -10, 10), (-10, false));
+        assert_eq!(clamp_number(10i8, -10, 10), (10, false));
+        assert_eq!(clamp_number(0u8, 5, u8::MAX), (5, true));
+        assert_eq!(clamp_number(u8::MAX, 0, u8::MAX), (u8::MAX, false));
+        assert_eq!(
+            clamp_number(i32::MIN, i32::MIN, i32::MAX),
+            (i32::MIN, false)
+        );
+        assert_eq!(clamp_number(-1.5f64, 0.0, 1.0), (0.0, true));
+        assert_eq!(
+            clamp_number(f64::MAX, f64::MIN, f64::MAX),
+            (f64::MAX, false)
+        );
+    }
+
+    #[test]
+    fn step_number_default_bounds_do_not_overflow() {
+        assert_eq!(step_number(0i32, 1, false, i32::MIN, i32::MAX), -1);
+        assert_eq!(step_number(-1i32, 1, true, i32::MIN, i32::MAX), 0);
+        assert_eq!(step_number(i32::MAX, 1, true, i32::MIN, i32::MAX), i32::MAX);
+        assert_eq!(
+            step_number(i32::MIN, 1, false, i32::MIN, i32::MAX),
+            i32::MIN
+        );
+        assert_eq!(step_number(i8::MAX - 1, 5, true, i8::MIN, i8::MAX), i8::MAX);
+        assert_eq!(
+            step_number(i8::MIN + 1, 5, false, i8::MIN, i8::MAX),
+            i8::MIN
+        );
+        assert_eq!(step_number(0u8, 1, false, u8::MIN, u8::MAX), 0);
+        assert_eq!(step_number(250u8, 10, true, u8::MIN, u8::MAX), u8::MAX);
+        assert_eq!(
+            step_number(f64::MAX, 1.0, true, f64::MIN, f64::MAX),
+            f64::MAX
+        );
+        assert_eq!(
+            step_number(f64::MIN, 1.0, false, f64::MIN, f64::MAX),
+            f64::MIN
+        );
+    }
+
+    #[test]
+    fn step_number_custom_bounds() {
+        assert_eq!(step_number(8i32, 5, true, 0, 10), 10);
+        assert_eq!(step_number(2i32, 5, false, 0, 10), 0);
+        assert_eq!(step_number(4i32, 5, true, 0, 10), 9);
+        assert_eq!(step_number(20i32, 1, false, 0, 10), 9);
+        assert_eq!(step_number(3u8, 2, false, 1, 5), 1);
+        assert_eq!(step_number(0.5f64, 0.25, true, 0.0, 1.0), 0.75);
+        assert_eq!(step_number(0.9f64, 0.25, true, 0.0, 1.0), 1.0);
+    }
+
+    #[test]
+    fn default_page_step_saturates() {
+        assert_eq!(default_page_step(1i32), 10);
+        assert_eq!(default_page_step(2.5f64), 25.0);
+        assert_eq!(default_page_step(100u8), u8::MAX);
+        assert_eq!(default_page_step(i8::MAX), i8::MAX);
+    }
+
+    #[test]
+    fn number_state_represents_value() {
+        let mut state = NumberState::new(5i32, 0, 100, 0);
+        assert!(state.represents(5));
+        assert!(!state.represents(6));
+
+        state.set("");
+        assert!(state.represents(0));
+        assert!(!state.represents(5));
+
+        let state = NumberState::new(500i32, 0, 100, 0);
+        assert!(state.represents(500));
+        assert!(state.represents(100));
+
+        let mut state = NumberState::new(1.5f64, 0.0, 10.0, 2);
+        state.set("2.");
+        assert!(state.represents(2.0));
+        assert!(!state.represents(1.5));
+    }
+
+    #[test]
+    fn reset_number_resyncs_display() {
+        let mut display = NumberState::new(7i32, 0, 100, 0);
+        display.set("42");
+        assert_eq!(resynced_number_state(&display, 42, 0, 100, 0), None);
+
+        let display = resynced_number_state(&display, 7, 0, 100, 0).unwrap();
+        assert_eq!(display.to_string(), "7");
+        assert_eq!(resynced_number_state(&display, 7, 0, 100, 0), None);
     }
 }
@@ -0,0 +1,602 @@
+use super::*;
+use crate::util::*;
+use gloo_timers::callback::Timeout;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use yew::platform::spawn_local;
+use yew::prelude::*;
+
+/// The distance in pixels from the bottom of a dropdown within which the next
+/// page of options is loaded.
+pub(crate) const LOAD_MORE_THRESHOLD_PX: i32 = 32;
+
+/// A page of options fetched from an option source.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptionPage<T> {
+    /// The options within the page.
+    pub options: Vec<T>,
+    /// Whether there are further pages of options after this one.
+    pub has_more: bool,
+}
+
+impl<T> OptionPage<T> {
+    /// Creates a new page of options.
+    pub fn new(options: Vec<T>, has_more: bool) -> Self {
+        Self { options, has_more }
+    }
+}
+
+/// A future resolving to a page of options, or an error message if the
+/// options could not be fetched.
+pub type OptionFuture<T> = Pin<Box<dyn Future<Output = Result<OptionPage<T>, String>>>>;
+
+/// A trait for fetching options asynchronously, e.g. when there are too many
+/// options to send to the client at once.
+pub trait OptionSource<T> {
+    /// Fetches a page of options matching a search query. Pages are numbered
+    /// from zero.
+    fn fetch(&self, query: &str, page: usize) -> OptionFuture<T>;
+}
+
+impl<T, F, Fut> OptionSource<T> for F
+where
+    F: Fn(&str, usize) -> Fut,
+    Fut: Future<Output = Result<OptionPage<T>, String>> + 'static,
+{
+    fn fetch(&self, query: &str, page: usize) -> OptionFuture<T> {
+        Box::pin(self(query, page))
+    }
+}
+
+/// An option source backed by a list of options held in memory. Options are
+/// matched against the query the same way a filterable select matches them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InMemoryOptionSource<T> {
+    /// The complete list of options.
+    options: Vec<T>,
+    /// The number of options in each page.
+    page_size: usize,
+}
+
+impl<T> InMemoryOptionSource<T> {
+    /// Creates a new in-memory option source.
+    pub fn new(options: Vec<T>, page_size: usize) -> Self {
+        Self {
+            options,
+            page_size: page_size.max(1),
+        }
+    }
+}
+
+impl<T: SelectItem + Clone + 'static> OptionSource<T> for InMemoryOptionSource<T> {
+    fn fetch(&self, query: &str, page: usize) -> OptionFuture<T> {
+        let mut matches = self
+            .options
+            .iter()
+            .filter_map(|option| {
+                if query.is_empty() {
+                    Some((0, option))
+                } else {
+                    option_match(&option.label(), query).map(|score| (score, option))
+                }
+            })
+            .collect::<Vec<_>>();
+
+        matches.sort_by_key(|(score, _)| *score);
+
+        let start = page * self.page_size;
+        let options = matches
+            .iter()
+            .skip(start)
+            .take(self.page_size)
+            .map(|(_, option)| (*option).clone())
+            .collect();
+        let has_more = matches.len() > start + self.page_size;
+
+        Box::pin(std::future::ready(Ok(OptionPage::new(options, has_more))))
+    }
+}
+
+/// A shared option source, for use as a component property.
+pub struct SharedOptionSource<T>(Rc<dyn OptionSource<T>>);
+
+impl<T> SharedOptionSource<T> {
+    /// Creates a new shared option source.
+    pub fn new(source: impl OptionSource<T> + 'static) -> Self {
+        Self(Rc::new(source))
+    }
+
+    /// Fetches a page of options matching a search query.
+    pub fn fetch(&self, query: &str, page: usize) -> OptionFuture<T> {
+        self.0.fetch(query, page)
+    }
+}
+
+impl<T> Clone for SharedOptionSource<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> PartialEq for SharedOptionSource<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// The options loaded from an option source.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct OptionLoadState<T> {
+    /// The query the options were loaded for, or `None` if nothing has been
+    /// loaded yet.
+    pub query: Option<String>,
+    /// The options loaded so far.
+    pub options: Vec<T>,
+    /// The number of pages loaded so far.
+    pub pages: usize,
+    /// Whether there are further pages of options to load.
+    pub has_more: bool,
+    /// Whether a page is currently being loaded.
+    pub loading: bool,
+    /// The error message from the most recent failed load.
+    pub error: Option<String>,
+    /// The generation of the current query. Results from fetches started for
+    /// an earlier query are discarded.
+    pub generation: usize,
+}
+
+impl<T> Default for OptionLoadState<T> {
+    fn default() -> Self {
+        Self {
+            query: None,
+            options: Vec::new(),
+            pages: 0,
+            has_more: false,
+            loading: false,
+            error: None,
+            generation: 0,
+        }
+    }
+}
+
+/// An update to the options loaded from an option source.
+pub(crate) enum OptionLoadAction<T> {
+    /// A page of options has started loading. Loading the first page starts
+    /// over with a new query.
+    Fetch {
+        /// The generation of the query.
+        generation: usize,
+        /// The query being loaded.
+        query: String,
+        /// The page being loaded.
+        page: usize,
+    },
+    /// A page of options has finished loading.
+    Loaded {
+        /// The generation of the query.
+        generation: usize,
+        /// The page that was loaded.
+        page: usize,
+        /// The loaded page, or an error message.
+        result: Result<OptionPage<T>, String>,
+    },
+}
+
+impl<T: Clone> Reducible for OptionLoadState<T> {
+    type Action = OptionLoadAction<T>;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        match action {
+            OptionLoadAction::Fetch {
+                generation,
+                query,
+                page: 0,
+            } => Rc::new(Self {
+                query: Some(query),
+                loading: true,
+                generation,
+                ..Self::default()
+            }),
+            OptionLoadAction::Fetch { generation, .. } if generation == self.generation => {
+                Rc::new(Self {
+                    loading: true,
+                    error: None,
+                    ..(*self).clone()
+                })
+            }
+            // Pages loaded out of order, or for an earlier query, are
+            // discarded.
+            OptionLoadAction::Loaded {
+                generation,
+                page,
+                result,
+            } if generation == self.generation && page == self.pages => match result {
+                Ok(loaded) => {
+                    let mut options = self.options.clone();
+                    options.extend(loaded.options);
+
+                    Rc::new(Self {
+                        options,
+                        pages: page + 1,
+                        has_more: loaded.has_more,
+                        loading: false,
+                        error: None,
+                        ..(*self).clone()
+                    })
+                }
+                Err(error) => Rc::new(Self {
+                    loading: false,
+                    error: Some(error),
+                    ..(*self).clone()
+                }),
+            },
+            OptionLoadAction::Fetch { .. } | OptionLoadAction::Loaded { .. } => self,
+        }
+    }
+}
+
+/// Starts fetching a page of options, updating the load state as it
+/// progresses.
+fn fetch_option_page<T: Clone + 'static>(
+    source: &SharedOptionSource<T>,
+    state: UseReducerHandle<OptionLoadState<T>>,
+    generation: usize,
+    query: String,
+    page: usize,
+) {
+    let future = source.fetch(&query, page);
+
+    state.dispatch(OptionLoadAction::Fetch {
+        generation,
+        query,
+        page,
+    });
+
+    spawn_local(async move {
+        let result = future.await;
+
+        state.dispatch(OptionLoadAction::Loaded {
+            generation,
+            page,
+            result,
+        });
+    });
+}
+
+/// A handle to the options loaded from an option source.
+#[derive(Clone)]
+pub(crate) struct UseOptionSourceHandle<T: Clone + 'static> {
+    /// The option source, if there is one.
+    source: Option<SharedOptionSource<T>>,
+    /// The load state.
+    state: UseReducerHandle<OptionLoadState<T>>,
+}
+
+impl<T: Clone + 'static> UseOptionSourceHandle<T> {
+    /// Whether options are being loaded from a source at all.
+    pub fn active(&self) -> bool {
+        self.source.is_some()
+    }
+
+    /// Gets the options loaded so far.
+    pub fn options(&self) -> Vec<T> {
+        self.state.options.clone()
+    }
+
+    /// Whether a page is currently being loaded.
+    pub fn loading(&self) -> bool {
+        self.state.loading
+    }
+
+    /// Gets the error message from the most recent failed load.
+    pub fn error(&self) -> Option<String> {
+        self.state.error.clone()
+    }
+
+    /// Starts loading the next page of options, if there is one and nothing
+    /// is loading already.
+    pub fn load_more(&self) {
+        if let (Some(source), Some(query)) = (&self.source, &self.state.query) {
+            if self.state.has_more && !self.state.loading && self.state.error.is_none() {
+                fetch_option_page(
+                    source,
+                    self.state.clone(),
+                    self.state.generation,
+                    query.clone(),
+                    self.state.pages,
+                );
+            }
+        }
+    }
+}
+
+/// Loads options matching a query from an option source. Queries are
+/// debounced, and nothing is loaded while `enabled` is false. Options already
+/// loaded for the query are kept rather than loaded again.
+#[hook]
+pub(crate) fn use_option_source<T>(
+    source: Option<SharedOptionSource<T>>,
+    query: String,
+    enabled: bool,
+    debounce_ms: u32,
+) -> UseOptionSourceHandle<T>
+where
+    T: Clone + 'static,
+{
+    let state = use_reducer(OptionLoadState::default);
+    let generation = use_mut_ref(|| 0usize);
+    let timeout = use_mut_ref(|| Option::<Timeout>::None);
+
+    {
+        let state = state.clone();
+        use_effect_with_deps(
+            move |(source, query, enabled): &(Option<SharedOptionSource<T>>, String, bool)| {
+                let loaded =
+                    state.query.as_ref() == Some(query) && !state.loading && state.error.is_none();
+
+                if let (Some(source), true, false) = (source.clone(), *enabled, loaded) {
+                    *generation.borrow_mut() += 1;
+                    let this_generation = *generation.borrow();
+                    let query = query.clone();
+
+                    *timeout.borrow_mut() = Some(Timeout::new(debounce_ms, move || {
+                        fetch_option_page(&source, state, this_generation, query, 0);
+                    }));
+                }
+
+                move || {
+                    timeout.borrow_mut().take(); // timeout is cancelled when dropped
+                }
+            },
+            (source.clone(), query, enabled),
+        );
+    }
+
+    UseOptionSourceHandle { source, state }
+}
+
+/// Renders the loading, error and empty states of a dropdown, using classes
+/// with the given prefix.
+pub(crate) fn dropdown_status_html(
+    prefix: &str,
+    loading: bool,
+    error: Option<String>,
+    empty: bool,
+) -> Html {
+    if loading {
+        html! {
+            <div class={format!("{prefix}-loading")}>
+                <Spinner size={SpinnerSize::Small} />
+            </div>
+        }
+    } else if let Some(error) = error {
+        html! {
+            <div role="alert" class={format!("{prefix}-error")}>{error}</div>
+        }
+    } else if empty {
+        html! {
+            <div class={format!("{prefix}-empty")}>{"No results"}</div>
+        }
+    } else {
+        html! {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+
+    /// A waker that does nothing, as in-memory fetches are never woken.
+    struct NoopWaker;
+
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    /// Fetches a page from a source whose futures are immediately ready.
+    fn fetch_now<T>(source: &impl OptionSource<T>, query: &str, page: usize) -> OptionPage<T> {
+        let waker = Waker::from(Arc::new(NoopWaker));
+
+        match source
+            .fetch(query, page)
+            .as_mut()
+            .poll(&mut Context::from_waker(&waker))
+        {
+            Poll::Ready(result) => result.unwrap(),
+            Poll::Pending => panic!("in-memory fetch is pending"),
+        }
+    }
+
+    fn page(options: &[&str], has_more: bool) -> Result<OptionPage<String>, String> {
+        Ok(OptionPage::new(
+            options.iter().map(|option| (*option).to_owned()).collect(),
+            has_more,
+        ))
+    }
+
+    fn fetch(generation: usize, query: &str, page: usize) -> OptionLoadAction<String> {
+        OptionLoadAction::Fetch {
+            generation,
+            query: query.to_owned(),
+            page,
+        }
+    }
+
+    fn loaded(
+        generation: usize,
+        page: usize,
+        result: Result<OptionPage<String>, String>,
+    ) -> OptionLoadAction<String> {
+        OptionLoadAction::Loaded {
+            generation,
+            page,
+            result,
+        }
+    }
+
+    fn options(state: &OptionLoadState<String>) -> Vec<&str> {
+        state.options.iter().map(String::as_str).collect()
+    }
+
+    #[test]
+    fn pages_are_appended_in_order() {
+        let state = Rc::new(OptionLoadState::default())
+            .reduce(fetch(1, "a", 0))
+            .reduce(loaded(1, 0, page(&["Apple", "Avocado"], true)));
+
+        assert!(!state.loading);
+        assert!(state.has_more);
+        assert_eq!(options(&state), ["Apple", "Avocado"]);
+
+        let state = state
+            .reduce(fetch(1, "a", 1))
+            .reduce(loaded(1, 1, page(&["Banana"], true)));
+
+        assert_eq!(state.pages, 2);
+        assert_eq!(options(&state), ["Apple", "Avocado", "Banana"]);
+    }
+
+    #[test]
+    fn stale_generation_is_discarded() {
+        let state = Rc::new(OptionLoadState::default())
+            .reduce(fetch(1, "a", 0))
+            .reduce(fetch(2, "ab", 0))
+            .reduce(loaded(1, 0, page(&["Apple"], false)));
+
+        assert!(state.loading);
+        assert_eq!(state.query.as_deref(), Some("ab"));
+        assert!(state.options.is_empty());
+
+        // Loading more for the stale query does nothing either.
+        let stale_fetch = state.clone().reduce(fetch(1, "a", 1));
+        assert_eq!(stale_fetch, state);
+
+        let state = state.reduce(loaded(2, 0, page(&["Abacus"], false)));
+        assert_eq!(options(&state), ["Abacus"]);
+    }
+
+    #[test]
+    fn out_of_order_page_is_discarded() {
+        let state = Rc::new(OptionLoadState::default())
+            .reduce(fetch(1, "", 0))
+            .reduce(loaded(1, 0, page(&["Apple"], true)))
+            .reduce(fetch(1, "", 1));
+        let skipped = state.clone().reduce(loaded(1, 2, page(&["Cherry"], true)));
+
+        assert_eq!(skipped, state);
+
+        let repeated = state.clone().reduce(loaded(1, 0, page(&["Apple"], true)));
+        assert_eq!(repeated, state);
+    }
+
+    #[test]
+    fn error_then_retry() {
+        let state = Rc::new(OptionLoadState::default())
+            .reduce(fetch(1, "", 0))
+            .reduce(loaded(1, 0, page(&["Apple"], true)))
+            .reduce(fetch(1, "", 1))
+            .reduce(loaded(1, 1, Err("Network error".to_owned())));
+
+        assert!(!state.loading);
+        assert_eq!(state.error.as_deref(), Some("Network error"));
+        assert_eq!(state.pages, 1);
+        assert_eq!(options(&state), ["Apple"]);
+
+        let state = state.reduce(fetch(1, "", 1));
+        assert!(state.loading);
+        assert_eq!(state.error, None);
+
+        let state = state.reduce(loaded(1, 1, page(&["Banana"], false)));
+        assert_eq!(state.error, None);
+        assert_eq!(options(&state), ["Apple", "Banana"]);
+    }
+
+    #[test]
+    fn end_of_results() {
+        let state = Rc::new(OptionLoadState::default())
+            .reduce(fetch(1, "", 0))
+            .reduce(loaded(1, 0, page(&["Apple"], true)))
+            .reduce(fetch(1, "", 1))
+            .reduce(loaded(1, 1, page(&[], false)));
+
+        assert!(!state.has_more);
+        assert!(!state.loading);
+        assert_eq!(state.pages, 2);
+        assert_eq!(options(&state), ["Apple"]);
+    }
+
+    #[test]
+    fn new_query_starts_over() {
+        let state = Rc::new(OptionLoadState::default())
+            .reduce(fetch(1, "a", 0))
+            .reduce(loaded(1, 0, page(&["Apple"], true)))
+            .reduce(fetch(2, "b", 0));
+
+        assert_eq!(state.generation, 2);
+        assert_eq!(state.pages, 0);
+        assert!(state.options.is_empty());
+        assert!(!state.has_more);
+    }
+
+    #[test]
+    fn in_memory_source_pages() {
+        let source = InMemoryOptionSource::new(vec!["a", "b", "c", "d", "e"], 2);
+
+        assert_eq!(
+            fetch_now(&source, "", 0),
+            OptionPage::new(vec!["a", "b"], true)
+        );
+        assert_eq!(
+            fetch_now(&source, "", 1),
+            OptionPage::new(vec!["c", "d"], true)
+        );
+        assert_eq!(fetch_now(&source, "", 2), OptionPage::new(vec!["e"], false));
+        assert_eq!(fetch_now(&source, "", 3), OptionPage::new(vec![], false));
+    }
+
+    #[test]
+    fn in_memory_source_exact_final_page() {
+        let source = InMemoryOptionSource::new(vec!["a", "b", "c", "d"], 2);
+
+        assert_eq!(
+            fetch_now(&source, "", 1),
+            OptionPage::new(vec!["c", "d"], false)
+        );
+    }
+
+    #[test]
+    fn in_memory_source_page_size_is_at_least_one() {
+        let source = InMemoryOptionSource::new(vec!["a", "b"], 0);
+
+        assert_eq!(fetch_now(&source, "", 0), OptionPage::new(vec!["a"], true));
+    }
+
+    #[test]
+    fn in_memory_source_filters_and_ranks() {
+        let source =
+            InMemoryOptionSource::new(vec!["Blueberry", "Banana", "Cherry", "Bean", "BERRY"], 10);
+
+        assert_eq!(
+            fetch_now(&source, "berry", 0),
+            OptionPage::new(vec!["BERRY", "Blueberry"], false)
+        );
+        assert_eq!(fetch_now(&source, "xyz", 0), OptionPage::new(vec![], false));
+    }
+
+    #[test]
+    fn in_memory_source_pages_filtered_options() {
+        let source = InMemoryOptionSource::new(vec!["Ape", "Bat", "Bee", "Cat"], 1);
+
+        assert_eq!(
+            fetch_now(&source, "at", 0),
+            OptionPage::new(vec!["Bat"], true)
+        );
+        assert_eq!(
+            fetch_now(&source, "at", 1),
+            OptionPage::new(vec!["Cat"], false)
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use super::*;
 use crate::util::*;
 use yew::prelude::*;
 
@@ -48,13 +49,39 @@ impl RadioGroupOrientation {
 #[derive(Properties, PartialEq, Clone)]
 pub struct RadioGroupProps {
     /// The radio group state.
-    pub state: UseStateHandle<Option<usize>>,
+    #[prop_or_default]
+    pub state: Option<UseStateHandle<Option<usize>>>,
+    /// The selected radio button index. Used along with `on_change` in place of
+    /// `state`.
+    #[prop_or_default]
+    pub value: Option<Option<usize>>,
+    /// The callback called when the selection changes.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_change: Callback<Option<usize>>,
+    /// The callback called when the radio group loses focus.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_blur: Callback<()>,
+    /// The name of the field within a surrounding form.
+    #[prop_or_default]
+    pub name: String,
     /// The orientation of the radio group.
     #[prop_or_default]
     pub orientation: RadioGroupOrientation,
+    /// The radio group label.
+    #[prop_or_default]
+    pub label: String,
     /// Whether a selection is required.
     #[prop_or(false)]
     pub required: bool,
+    /// Whether to mark the radio group as optional when it is not required.
+    #[prop_or(false)]
+    pub show_optional: bool,
+    /// Help text describing the radio group.
+    #[prop_or_default]
+    pub help: String,
+    /// An optional error message.
+    #[prop_or_default]
+    pub error: Option<String>,
     /// Whether the radio group is disabled.
     #[prop_or(false)]
     pub disabled: bool,
@@ -67,12 +94,40 @@ pub struct RadioGroupProps {
 pub fn RadioGroup(props: &RadioGroupProps) -> Html {
     let RadioGroupProps {
         state,
+        value,
+        on_change,
+        on_blur,
+        name: field_name,
         orientation,
+        label,
         required,
+        show_optional,
+        help,
+        error,
         disabled,
         children,
     } = props.clone();
 
+    let state = use_value_handle(state, value, on_change);
+
+    let first_node = use_node_ref();
+    let form_error = use_form_registration(
+        &field_name,
+        FormField {
+            label: label.clone(),
+            node: first_node.clone(),
+            ..FormField::new(FormValue::Selection(*state), required, error.clone())
+        },
+    );
+    use_form_restore(&field_name, {
+        let state = state.clone();
+        move |value| {
+            if let FormValue::Selection(value) = value {
+                state.set(value);
+            }
+        }
+    });
+    let error = error.or(form_error);
     let name_state = use_state(new_id);
     let name = (*name_state).clone();
     let id_states = use_state(|| {
@@ -82,6 +137,15 @@ pub fn RadioGroup(props: &RadioGroupProps) -> Html {
             .collect::<Vec<_>>()
     });
     let ids = (*id_states).clone();
+    let group_node = use_node_ref();
+    let onfocusout = {
+        let group_node_local = group_node.clone();
+        move |event: FocusEvent| {
+            if focus_leaving(&event, &group_node_local) {
+                on_blur.emit(());
+            }
+        }
+    };
     let orientation_class = format!("base-radio-group-{}", orientation.orientation_name());
 
     let new_children = children
@@ -96,6 +160,11 @@ pub fn RadioGroup(props: &RadioGroupProps) -> Html {
             let id = ids[index].clone();
             let checked = state.filter(|value| *value == index).is_some();
             let this_disabled = disabled || child_disabled;
+            let node = if index == 0 {
+                first_node.clone()
+            } else {
+                NodeRef::default()
+            };
             let child_state = state.clone();
             let oninput = move |_| {
                 child_state.set(Some(index));
@@ -104,6 +173,7 @@ pub fn RadioGroup(props: &RadioGroupProps) -> Html {
             html! {
                 <div class={classes!("base-radio-option", this_disabled.then_some("base-radio-option-disabled"))}>
                     <input
+                        ref={node}
                         type="radio"
                         id={id.clone()}
                         name={name.clone()}
@@ -121,8 +191,20 @@ pub fn RadioGroup(props: &RadioGroupProps) -> Html {
         .collect::<Html>();
 
     html! {
-        <div class={classes!("base-radio-group", orientation_class)}>
-            {new_children}
-        </div>
+        <FieldWrapper
+            id={ids.first().cloned().unwrap_or_default()}
+            {label}
+            {required}
+            {show_optional}
+            {help}
+            {error}
+            {disabled}
+            class="base-radio-group-container"
+            label_class="base-radio-group-label"
+        >
+            <div ref={group_node} {onfocusout} class={classes!("base-radio-group", orientation_class)}>
+                {new_children}
+            </div>
+        </FieldWrapper>
     }
 }
@@ -1,152 +1,968 @@
 use super::*;
 use crate::util::*;
+use std::ops::Range;
 use yew::prelude::*;
 use yew_hooks::use_click_away;
 
-/// Select option properties.
-#[derive(Properties, PartialEq, Clone)]
-pub struct SelectOptionProps {
-    /// Whether the select option is disabled.
-    #[prop_or(false)]
-    pub disabled: bool,
-    /// Child elements.
-    pub children: Children,
+/// An item that can be chosen from a select component.
+pub trait SelectItem {
+    /// Gets the label displayed for the item.
+    fn label(&self) -> String;
+
+    /// Whether the item is disabled, preventing it from being chosen.
+    fn disabled(&self) -> bool {
+        false
+    }
 }
 
-/// A select option component.
-#[function_component]
-pub fn SelectOption(props: &SelectOptionProps) -> Html {
-    let SelectOptionProps { children, .. } = props.clone();
+impl SelectItem for String {
+    fn label(&self) -> String {
+        self.clone()
+    }
+}
+
+impl SelectItem for &'static str {
+    fn label(&self) -> String {
+        (*self).to_owned()
+    }
+}
+
+impl<T: FormEnum> SelectItem for T {
+    fn label(&self) -> String {
+        FormEnum::label(self)
+    }
+}
+
+/// Renders a select item, using the render callback if one is provided.
+fn render_select_item<T: SelectItem + Clone>(item: &T, render: &Option<Callback<T, Html>>) -> Html {
+    match render {
+        Some(render) => render.emit(item.clone()),
+        None => html! { item.label() },
+    }
+}
+
+/// A group of options within a select component's dropdown, displayed below
+/// a header that cannot be chosen. Groups are separated from the options
+/// preceding them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectOptionGroup<T> {
+    /// The group header label. No header is displayed if this is empty, in
+    /// which case the group is only marked by a separator.
+    pub label: String,
+    /// The options within the group.
+    pub options: Vec<T>,
+}
+
+impl<T> SelectOptionGroup<T> {
+    /// Creates a new option group.
+    pub fn new(label: impl Into<String>, options: Vec<T>) -> Self {
+        Self {
+            label: label.into(),
+            options,
+        }
+    }
+}
+
+/// A section of a select component's options, either the ungrouped options
+/// or a single group.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SelectSection {
+    /// The section header label.
+    pub label: String,
+    /// The range of option indices within the section.
+    pub range: Range<usize>,
+}
+
+/// Flattens ungrouped options and option groups into a single list of
+/// options, along with the sections of that list. Grouped options follow the
+/// ungrouped options, so this determines the index of each option.
+pub(crate) fn select_sections<T: Clone>(
+    options: &[T],
+    groups: &[SelectOptionGroup<T>],
+) -> (Vec<T>, Vec<SelectSection>) {
+    let mut all_options = options.to_vec();
+    let mut sections = vec![SelectSection {
+        label: String::new(),
+        range: 0..options.len(),
+    }];
+
+    for group in groups {
+        let start = all_options.len();
+        all_options.extend(group.options.iter().cloned());
+        sections.push(SelectSection {
+            label: group.label.clone(),
+            range: start..all_options.len(),
+        });
+    }
+
+    (all_options, sections)
+}
+
+/// A row within a select component's dropdown.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum SelectRow {
+    /// The null option.
+    Null,
+    /// A group header.
+    Header(String),
+    /// A separator between groups.
+    Separator,
+    /// A row offering to create a new option with the given label.
+    Create(String),
+    /// An option, along with the indices of the characters in its label
+    /// matching the search query.
+    Option {
+        /// The index of the option.
+        index: usize,
+        /// The indices of the matched characters.
+        matches: Vec<usize>,
+    },
+}
+
+impl SelectRow {
+    /// Gets the index of the option in this row, if the row is an option.
+    pub fn option_index(&self) -> Option<usize> {
+        match self {
+            Self::Option { index, .. } => Some(*index),
+            Self::Null | Self::Header(_) | Self::Separator | Self::Create(_) => None,
+        }
+    }
+}
+
+/// Gets the rows to display in a select component's dropdown. If a search
+/// query is given, only matching options are included, ordered within each
+/// section by the strength of the match. Sections without any matching
+/// options are omitted entirely.
+pub(crate) fn select_rows<T: SelectItem>(
+    options: &[T],
+    sections: &[SelectSection],
+    query: &str,
+    nullable: bool,
+) -> Vec<SelectRow> {
+    let mut rows = (nullable && query.is_empty())
+        .then_some(SelectRow::Null)
+        .into_iter()
+        .collect::<Vec<_>>();
+
+    for (section_index, section) in sections.iter().enumerate() {
+        let mut matches = section
+            .range
+            .clone()
+            .filter_map(|index| {
+                if query.is_empty() {
+                    Some((0, Vec::new(), index))
+                } else {
+                    option_match_indices(&options[index].label(), query)
+                        .map(|(score, matches)| (score, matches, index))
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if matches.is_empty() {
+            continue;
+        }
+
+        matches.sort_by_key(|(score, _, _)| *score);
+
+        if section_index > 0 && !rows.is_empty() {
+            rows.push(SelectRow::Separator);
+        }
+
+        if !section.label.is_empty() {
+            rows.push(SelectRow::Header(section.label.clone()));
+        }
+
+        rows.extend(
+            matches
+                .into_iter()
+                .map(|(_, matches, index)| SelectRow::Option { index, matches }),
+        );
+    }
+
+    rows
+}
+
+/// Gets the label of a new option that could be created from a search query,
+/// if the query is not blank and does not already match an option exactly.
+pub(crate) fn creatable_value<'a, T: SelectItem + 'a>(
+    options: impl IntoIterator<Item = &'a T>,
+    query: &str,
+) -> Option<String> {
+    let query = query.trim();
+    let lower_query = query.to_lowercase();
+
+    (!query.is_empty()
+        && !options
+            .into_iter()
+            .any(|option| option.label().to_lowercase() == lower_query))
+    .then(|| query.to_owned())
+}
+
+/// Gets the label of a row offering to create a new option.
+pub(crate) fn create_option_label(value: &str) -> String {
+    format!("Create '{value}'")
+}
+
+/// The options of a select component's dropdown, along with the rows
+/// displaying them and the listbox entries for those rows.
+pub(crate) struct SelectDropdown<T> {
+    /// All options, with grouped options following the ungrouped options.
+    pub options: Vec<T>,
+    /// The sections the options are divided into.
+    pub sections: Vec<SelectSection>,
+    /// The rows matching the search query.
+    pub rows: Vec<SelectRow>,
+    /// The listbox entry for each row.
+    pub entries: Vec<ListboxEntry>,
+}
+
+/// Gets the listbox entry for a select component's dropdown row. Headers and
+/// separators are treated as disabled entries, so that keyboard navigation
+/// skips over them.
+pub(crate) fn select_row_entry<T: SelectItem>(
+    options: &[T],
+    null_label: &str,
+    row: &SelectRow,
+) -> ListboxEntry {
+    match row {
+        SelectRow::Null => ListboxEntry::new(null_label, false),
+        SelectRow::Header(_) | SelectRow::Separator => ListboxEntry::new("", true),
+        SelectRow::Create(value) => ListboxEntry::new(create_option_label(value), false),
+        SelectRow::Option { index, .. } => {
+            ListboxEntry::new(options[*index].label(), options[*index].disabled())
+        }
+    }
+}
+
+/// Renders a group header or separator row within a select component's
+/// listbox. Nothing is rendered for any other row.
+pub(crate) fn select_divider_html(row: &SelectRow) -> Html {
+    match row {
+        SelectRow::Header(label) => html! {
+            <div role="presentation" class="base-select-group-header">{label}</div>
+        },
+        SelectRow::Separator => html! {
+            <div role="separator" class="base-select-separator"></div>
+        },
+        SelectRow::Null | SelectRow::Create(_) | SelectRow::Option { .. } => html! {},
+    }
+}
+
+/// Renders a single option within a select component's listbox.
+fn select_option_html(
+    listbox: &UseListboxHandle,
+    index: usize,
+    content: Html,
+    disabled: bool,
+    selected: bool,
+    choose: impl Fn(usize) + 'static,
+) -> Html {
+    let highlighted = listbox.highlighted() == Some(index);
+    let onclick = move |_| {
+        choose(index);
+    };
+    let onmouseenter = {
+        let listbox = listbox.clone();
+        move |_| {
+            if !disabled {
+                listbox.highlight(Some(index));
+            }
+        }
+    };
 
     html! {
-        <>
-            {children}
-        </>
+        <div
+            id={listbox.option_id(index)}
+            role="option"
+            aria-selected={selected.to_string()}
+            aria-disabled={disabled.to_string()}
+            {onclick}
+            {onmouseenter}
+            class={classes!(
+                "base-select-option",
+                disabled.then_some("base-select-option-disabled"),
+                selected.then_some("base-select-option-selected"),
+                highlighted.then_some("base-select-option-highlighted"),
+            )}
+        >
+            {content}
+        </div>
     }
 }
 
-/// Select properties.
+/// Properties shared by the select components.
 #[derive(Properties, PartialEq, Clone)]
-pub struct SelectProps {
-    /// The selection state.
-    pub state: UseStateHandle<usize>,
+struct SelectBaseProps<T: SelectItem + PartialEq + Clone + 'static> {
+    /// The selected option.
+    selected: Option<T>,
+    /// The callback called when an option, or the null option, is chosen.
+    on_select: Callback<Option<T>>,
+    /// The callback called when the selection loses focus.
+    on_blur: Callback<()>,
+    /// The name of the field within a surrounding form.
+    name: String,
+    /// The ungrouped options to choose from.
+    options: Vec<T>,
+    /// Groups of options to choose from.
+    groups: Vec<SelectOptionGroup<T>>,
+    /// A source to load options from in place of `options` and `groups`.
+    source: Option<SharedOptionSource<T>>,
+    /// The time in milliseconds to wait after the search query changes
+    /// before loading options from the source.
+    debounce_ms: u32,
+    /// A callback rendering an option.
+    render: Option<Callback<T, Html>>,
     /// The selection label.
-    #[prop_or_default]
-    pub label: String,
+    label: String,
+    /// The null option label, if there is a null option.
+    null_label: Option<String>,
     /// Whether a selection is required.
-    #[prop_or(false)]
-    pub required: bool,
+    required: bool,
+    /// Whether to mark the selection as optional when it is not required.
+    show_optional: bool,
+    /// Help text describing the selection.
+    help: String,
     /// An optional error message.
-    #[prop_or_default]
-    pub error: Option<String>,
+    error: Option<String>,
     /// Whether the selection is disabled.
-    #[prop_or(false)]
-    pub disabled: bool,
-    /// Child elements.
-    pub children: ChildrenWithProps<SelectOption>,
+    disabled: bool,
+    /// Whether the options can be narrowed down with a search field.
+    filterable: bool,
+    /// Search field placeholder text.
+    search_placeholder: String,
+    /// The heights of the rows in the dropdown, if the rows are virtualized.
+    row_height: Option<RowHeight>,
+    /// Whether the selection can be cleared.
+    clearable: bool,
+    /// The selection the field is reset to when cleared.
+    cleared: Option<T>,
+    /// The callback called when the selection is cleared.
+    on_clear: Callback<()>,
+    /// The callback called to create a new option from the search query.
+    on_create: Option<Callback<String>>,
 }
 
-/// A select component.
+/// The implementation of the select components.
 #[function_component]
-pub fn Select(props: &SelectProps) -> Html {
-    let SelectProps {
-        state,
+fn SelectBase<T: SelectItem + PartialEq + Clone + 'static>(props: &SelectBaseProps<T>) -> Html {
+    let SelectBaseProps {
+        selected,
+        on_select,
+        on_blur,
+        name,
+        options,
+        groups,
+        source,
+        debounce_ms,
+        render,
         label,
+        null_label,
         required,
+        show_optional,
+        help,
         error,
         disabled,
-        children,
+        filterable,
+        search_placeholder,
+        row_height,
+        clearable,
+        cleared,
+        on_clear,
+        on_create,
     } = props.clone();
 
+    let dropdown_open = use_state(|| false);
+    let open = *dropdown_open;
+    let query_state = use_state(String::new);
+    let query = if filterable {
+        (*query_state).clone()
+    } else {
+        String::new()
+    };
+    let remote = use_option_source(source, query.clone(), open, debounce_ms);
+
+    let creatable = filterable && on_create.is_some();
+    // Matching every option against the query is costly for long lists, so
+    // the rows are only rebuilt when the options or the query change.
+    let dropdown = use_memo(
+        |(options, groups, remote_options, query, null_label, creatable)| {
+            // Options loaded from a source are already filtered by the source.
+            let ((options, sections), row_query) = match remote_options {
+                Some(remote_options) => (select_sections(remote_options, &[]), ""),
+                None => (select_sections(options, groups), query.as_str()),
+            };
+            let mut rows = select_rows(&options, &sections, row_query, null_label.is_some());
+            rows.extend(
+                creatable
+                    .then(|| creatable_value(&options, query).map(SelectRow::Create))
+                    .flatten(),
+            );
+            let null_label = null_label.as_deref().unwrap_or_default();
+            let entries = rows
+                .iter()
+                .map(|row| select_row_entry(&options, null_label, row))
+                .collect();
+
+            SelectDropdown {
+                options,
+                sections,
+                rows,
+                entries,
+            }
+        },
+        (
+            options,
+            groups,
+            remote.active().then(|| remote.options()),
+            query.clone(),
+            null_label.clone(),
+            creatable,
+        ),
+    );
+    let selection = selected.as_ref().and_then(|selected| {
+        dropdown
+            .options
+            .iter()
+            .position(|option| option == selected)
+    });
+    let form_value = FormValue::Choice(selected.as_ref().map(SelectItem::label));
+    let button_node = use_node_ref();
+    let form_error = use_form_registration(
+        &name,
+        FormField {
+            label: label.clone(),
+            node: button_node.clone(),
+            // Options loaded from a source may not be loaded when a draft is
+            // restored, so their selection is not persisted.
+            persist: !remote.active(),
+            ..FormField::new(form_value, required, error.clone())
+        },
+    );
+    use_form_restore(&name, {
+        let on_select = on_select.clone();
+        let dropdown = dropdown.clone();
+        move |value| {
+            if let FormValue::Choice(label) = value {
+                on_select.emit(label.and_then(|label| {
+                    dropdown
+                        .options
+                        .iter()
+                        .find(|option| option.label() == label)
+                        .cloned()
+                }));
+            }
+        }
+    });
+    let error = error.or(form_error);
+
     let id_state = use_state(new_id);
     let id = (*id_state).clone();
-    let dropdown_open = use_state(|| false);
+    let search_node = use_node_ref();
+
+    let listbox = use_listbox();
+    let virtual_list = use_virtual_list(dropdown.rows.len(), row_height, listbox.highlighted());
+    let selected_row = dropdown.rows.iter().position(|row| match row {
+        SelectRow::Null => selection.is_none(),
+        row => row
+            .option_index()
+            .is_some_and(|index| selection == Some(index)),
+    });
+
+    use_effect_with_deps(
+        {
+            let search_node = search_node.clone();
+            move |open: &bool| {
+                if *open && filterable {
+                    focus_element(&search_node);
+                }
+            }
+        },
+        open,
+    );
+
+    let close = {
+        let dropdown_open = dropdown_open.clone();
+        let query_state = query_state.clone();
+        let button_node = button_node.clone();
+        move |refocus: bool| {
+            dropdown_open.set(false);
+            query_state.set(String::new());
+
+            if refocus && filterable {
+                focus_element(&button_node);
+            }
+        }
+    };
+    let choose = {
+        let on_select = on_select.clone();
+        let dropdown = dropdown.clone();
+        let close = close.clone();
+        move |row_index: usize| match dropdown.rows.get(row_index) {
+            Some(SelectRow::Null) => {
+                on_select.emit(None);
+                close(true);
+            }
+            Some(SelectRow::Option { index, .. }) if !dropdown.options[*index].disabled() => {
+                on_select.emit(Some(dropdown.options[*index].clone()));
+                close(true);
+            }
+            Some(SelectRow::Create(value)) => {
+                if let Some(on_create) = &on_create {
+                    on_create.emit(value.clone());
+                }
+
+                close(true);
+            }
+            _ => {}
+        }
+    };
 
     let on_button_click = {
-        let dropdown_open_local = dropdown_open.clone();
+        let dropdown_open = dropdown_open.clone();
+        let listbox = listbox.clone();
+        let dropdown = dropdown.clone();
+        let close = close.clone();
         move |_| {
             if !disabled {
-                dropdown_open_local.set(!*dropdown_open_local);
+                if open {
+                    close(false);
+                } else {
+                    listbox.open_at(selected_row, &dropdown.entries);
+                    dropdown_open.set(true);
+                }
+            }
+        }
+    };
+    let can_clear = clearable && selected != cleared;
+    let clear = {
+        let on_select = on_select.clone();
+        let button_node = button_node.clone();
+        Callback::from(move |_| {
+            on_select.emit(cleared.clone());
+            focus_element(&button_node);
+            on_clear.emit(());
+        })
+    };
+    let onkeydown = {
+        let dropdown_open = dropdown_open.clone();
+        let listbox = listbox.clone();
+        let dropdown = dropdown.clone();
+        let choose = choose.clone();
+        let close = close.clone();
+        let clear = clear.clone();
+        move |event: KeyboardEvent| {
+            if disabled {
+                return;
+            }
+
+            if can_clear && !open && event.key() == "Backspace" {
+                event.prevent_default();
+                clear.emit(());
+                return;
+            }
+
+            match listbox.handle_key(&event, open, &dropdown.entries) {
+                ListboxKey::Open => {
+                    listbox.open_at(selected_row, &dropdown.entries);
+                    dropdown_open.set(true);
+                }
+                ListboxKey::Close => close(false),
+                ListboxKey::Choose(index) => choose(index),
+                ListboxKey::Handled | ListboxKey::Ignored => {}
+            }
+        }
+    };
+
+    let on_search_input = {
+        let query_state = query_state.clone();
+        let listbox = listbox.clone();
+        let dropdown = dropdown.clone();
+        let null_label = null_label.clone();
+        let remote_active = remote.active();
+        move |event: InputEvent| {
+            let new_query = input_event_value(event);
+
+            if remote_active {
+                listbox.highlight(None);
+            } else {
+                let SelectDropdown {
+                    options, sections, ..
+                } = &*dropdown;
+                let new_entries = select_rows(options, sections, &new_query, null_label.is_some())
+                    .iter()
+                    .chain(
+                        creatable
+                            .then(|| creatable_value(options, &new_query).map(SelectRow::Create))
+                            .flatten()
+                            .as_ref(),
+                    )
+                    .map(|row| {
+                        select_row_entry(options, null_label.as_deref().unwrap_or_default(), row)
+                    })
+                    .collect::<Vec<_>>();
+                listbox.highlight(next_enabled(&new_entries, None, true));
             }
+
+            query_state.set(new_query);
+        }
+    };
+    let on_search_keydown = {
+        let listbox = listbox.clone();
+        let choose = choose.clone();
+        let close = close.clone();
+        let dropdown = dropdown.clone();
+        move |event: KeyboardEvent| match listbox.handle_search_key(&event, &dropdown.entries) {
+            ListboxKey::Choose(index) => choose(index),
+            ListboxKey::Close => close(event.key() == "Escape"),
+            ListboxKey::Open | ListboxKey::Handled | ListboxKey::Ignored => {}
         }
     };
 
     let select_node = use_node_ref();
     use_click_away(select_node.clone(), {
-        let dropdown_open_local = dropdown_open.clone();
+        let close = close.clone();
         move |_| {
-            dropdown_open_local.set(false);
+            if open {
+                close(false);
+            }
         }
     });
+    let onfocusout = {
+        let select_node_local = select_node.clone();
+        move |event: FocusEvent| {
+            if focus_leaving(&event, &select_node_local) {
+                on_blur.emit(());
+            }
+        }
+    };
 
-    let selected_child = if *state < children.len() {
-        children.iter().nth(*state).unwrap().into()
-    } else {
-        html! {
-            <SelectOption>{"Select..."}</SelectOption>
+    let on_popup_scroll = {
+        let remote = remote.clone();
+        let virtual_list = virtual_list.clone();
+        move |event: Event| {
+            virtual_list.on_scroll();
+
+            if scrolled_near_bottom(&event, LOAD_MORE_THRESHOLD_PX) {
+                remote.load_more();
+            }
         }
     };
 
-    let new_children = children
+    let selected_option = match &selected {
+        Some(selected) => render_select_item(selected, &render),
+        None => html! { null_label.clone().unwrap_or_else(|| "Select...".to_owned()) },
+    };
+
+    let row_range = virtual_list.range();
+    let row_list = dropdown
+        .rows
         .iter()
         .enumerate()
-        .map(|(index, child)| {
-            let SelectOptionProps {
-                disabled: child_disabled,
-                children: child_children,
-            } = (*child.props).clone();
-
-            let child_state = state.clone();
-            let child_dropdown_open = dropdown_open.clone();
-            let on_child_click = move |_| {
-                if !child_disabled {
-                    child_state.set(index);
-                    child_dropdown_open.set(false);
-                }
-            };
+        .skip(row_range.start)
+        .take(row_range.len())
+        .map(|(row_index, row)| match row {
+            SelectRow::Header(_) | SelectRow::Separator => select_divider_html(row),
+            SelectRow::Create(value) => select_option_html(
+                &listbox,
+                row_index,
+                html! { create_option_label(value) },
+                false,
+                false,
+                choose.clone(),
+            ),
+            SelectRow::Null => select_option_html(
+                &listbox,
+                row_index,
+                html! { null_label.clone().unwrap_or_default() },
+                false,
+                selection.is_none(),
+                choose.clone(),
+            ),
+            SelectRow::Option { index, matches } => {
+                let option = &dropdown.options[*index];
+                let content = match &render {
+                    Some(render) => render.emit(option.clone()),
+                    None => highlighted_label(&option.label(), matches),
+                };
 
-            html! {
-                <div onclick={on_child_click} class={classes!("base-select-option", child_disabled.then_some("base-select-option-disabled"))}>
-                    {child_children}
-                </div>
+                select_option_html(
+                    &listbox,
+                    row_index,
+                    content,
+                    option.disabled(),
+                    selection == Some(*index),
+                    choose.clone(),
+                )
             }
         })
         .collect::<Html>();
 
     html! {
-        <div class={classes!("base-select-container", disabled.then_some("base-select-container-disabled"), (*dropdown_open).then_some("base-select-container-open"))}>
-            <label for={id.clone()} class="base-select-label">
-                {label}
-                <span class="base-required-mark">{required.then_some(" *").unwrap_or_default()}</span>
-            </label>
-            <div ref={select_node} class="base-select">
+        <FieldWrapper
+            id={id.clone()}
+            {label}
+            {required}
+            {show_optional}
+            {help}
+            error={error.clone()}
+            {disabled}
+            class={classes!("base-select-container", disabled.then_some("base-select-container-disabled"), open.then_some("base-select-container-open"))}
+            label_class="base-select-label"
+        >
+            <div ref={select_node} {onfocusout} class="base-select">
                 <button
+                    ref={button_node}
+                    type="button"
                     {id}
                     onclick={on_button_click}
+                    {onkeydown}
                     {disabled}
+                    role="combobox"
+                    aria-haspopup="listbox"
+                    aria-expanded={open.to_string()}
+                    aria-controls={listbox.id()}
+                    aria-activedescendant={(!filterable).then(|| listbox.active_descendant(open)).flatten()}
                     class={classes!("base-select-button", error.clone().map(|_| "base-select-button-invalid"))}
                 >
                     <div class="base-select-button-selection">
-                        {selected_child}
+                        {selected_option}
                     </div>
                     <Icon name="angle-down-solid" {disabled} class="base-select-button-icon" />
                 </button>
+                {clear_button_html(can_clear, disabled, clear)}
                 <div class="base-select-dropdown">
-                    <div class="base-select-popup">
-                        {new_children}
+                    {filterable.then_some(html! {
+                        <div class="base-select-search">
+                            <input
+                                ref={search_node}
+                                type="text"
+                                value={query}
+                                placeholder={search_placeholder}
+                                oninput={on_search_input}
+                                onkeydown={on_search_keydown}
+                                role="combobox"
+                                aria-autocomplete="list"
+                                aria-expanded={open.to_string()}
+                                aria-controls={listbox.id()}
+                                aria-activedescendant={listbox.active_descendant(open)}
+                                class="base-select-search-input"
+                            />
+                        </div>
+                    })}
+                    <div
+                        id={listbox.id()}
+                        role="listbox"
+                        ref={virtual_list.viewport()}
+                        onmousedown={|event: MouseEvent| event.prevent_default()}
+                        onscroll={on_popup_scroll}
+                        class="base-select-popup"
+                    >
+                        {virtual_list.render(row_list)}
+                        {dropdown_status_html("base-select", remote.loading(), remote.error(), dropdown.rows.is_empty())}
                     </div>
                 </div>
             </div>
-            <Error message={error} size={ErrorSize::Small} />
-        </div>
+        </FieldWrapper>
+    }
+}
+
+/// Select properties.
+#[derive(Properties, PartialEq, Clone)]
+pub struct SelectProps<T: SelectItem + PartialEq + Clone + 'static> {
+    /// The selection state.
+    #[prop_or_default]
+    pub state: Option<UseStateHandle<T>>,
+    /// The selected option. Used along with `on_change` in place of `state`.
+    #[prop_or_default]
+    pub value: Option<T>,
+    /// The callback called when the selection changes.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_change: Callback<T>,
+    /// The callback called when the selection loses focus.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_blur: Callback<()>,
+    /// The name of the field within a surrounding form.
+    #[prop_or_default]
+    pub name: String,
+    /// The ungrouped options to choose from.
+    #[prop_or_default]
+    pub options: Vec<T>,
+    /// Groups of options to choose from, displayed after the ungrouped
+    /// options. Grouped options follow the ungrouped options when counting
+    /// option indices.
+    #[prop_or_default]
+    pub groups: Vec<SelectOptionGroup<T>>,
+    /// A source to load options from in place of `options` and `groups`. The
+    /// search query is passed to the source when the options are filterable.
+    #[prop_or_default]
+    pub source: Option<SharedOptionSource<T>>,
+    /// The time in milliseconds to wait after the search query changes
+    /// before loading options from the source.
+    #[prop_or(300)]
+    pub debounce_ms: u32,
+    /// A callback rendering an option. Option labels are displayed if this is
+    /// not provided.
+    #[prop_or_default]
+    pub render: Option<Callback<T, Html>>,
+    /// The selection label.
+    #[prop_or_default]
+    pub label: String,
+    /// Whether a selection is required.
+    #[prop_or(false)]
+    pub required: bool,
+    /// Whether to mark the selection as optional when it is not required.
+    #[prop_or(false)]
+    pub show_optional: bool,
+    /// Help text describing the selection.
+    #[prop_or_default]
+    pub help: String,
+    /// An optional error message.
+    #[prop_or_default]
+    pub error: Option<String>,
+    /// Whether the selection is disabled.
+    #[prop_or(false)]
+    pub disabled: bool,
+    /// Whether the options can be narrowed down with a search field in the
+    /// dropdown.
+    #[prop_or(false)]
+    pub filterable: bool,
+    /// Search field placeholder text.
+    #[prop_or("Search...".to_owned())]
+    pub search_placeholder: String,
+    /// The heights of the rows in the dropdown. Only the rows scrolled into
+    /// view are rendered if this is set, keeping long lists of options
+    /// responsive.
+    #[prop_or_default]
+    pub row_height: Option<RowHeight>,
+    /// Whether the selection can be cleared with an inline clear button or
+    /// the Backspace key. Since a selection is always present, clearing
+    /// resets the selection to the first option. Selections of options
+    /// loaded from a source cannot be cleared. Use `SelectWithNull` to allow
+    /// clearing the selection entirely.
+    #[prop_or(false)]
+    pub clearable: bool,
+    /// The callback called when the selection is cleared.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_clear: Callback<()>,
+    /// The callback called with the search query when the user chooses to
+    /// create a new option from it. A row offering to create the option is
+    /// displayed when the query matches no option exactly. The dropdown is
+    /// closed, and the callback is responsible for adding the option, which
+    /// may happen asynchronously, and selecting it. Only used when the
+    /// options are filterable.
+    #[prop_or_default]
+    pub on_create: Option<Callback<String>>,
+}
+
+/// A select component. The first option is selected if no selection is
+/// provided.
+#[function_component]
+pub fn Select<T: SelectItem + PartialEq + Clone + 'static>(props: &SelectProps<T>) -> Html {
+    let SelectProps {
+        state,
+        value,
+        on_change,
+        on_blur,
+        name,
+        options,
+        groups,
+        source,
+        debounce_ms,
+        render,
+        label,
+        required,
+        show_optional,
+        help,
+        error,
+        disabled,
+        filterable,
+        search_placeholder,
+        row_height,
+        clearable,
+        on_clear,
+        on_create,
+    } = props.clone();
+
+    let fallback = options
+        .first()
+        .or_else(|| groups.iter().find_map(|group| group.options.first()))
+        .cloned();
+    let state = use_value_handle_or(state, value, on_change, fallback.clone());
+
+    let selected = state.as_deref().cloned();
+    let on_select = Callback::from(move |option: Option<T>| {
+        if let (Some(state), Some(option)) = (&state, option) {
+            state.set(option);
+        }
+    });
+
+    html! {
+        <SelectBase<T>
+            {selected}
+            {on_select}
+            {on_blur}
+            {name}
+            {options}
+            {groups}
+            {source}
+            {debounce_ms}
+            {render}
+            {label}
+            null_label={None::<String>}
+            {required}
+            {show_optional}
+            {help}
+            {error}
+            {disabled}
+            {filterable}
+            {search_placeholder}
+            {row_height}
+            clearable={clearable && fallback.is_some()}
+            cleared={fallback}
+            {on_clear}
+            {on_create}
+        />
     }
 }
 
 /// Select with null option properties.
 #[derive(Properties, PartialEq, Clone)]
-pub struct SelectWithNullProps {
+pub struct SelectWithNullProps<T: SelectItem + PartialEq + Clone + 'static> {
     /// The selection state.
-    pub state: UseStateHandle<Option<usize>>,
+    #[prop_or_default]
+    pub state: Option<UseStateHandle<Option<T>>>,
+    /// The selected option. Used along with `on_change` in place of `state`.
+    #[prop_or_default]
+    pub value: Option<Option<T>>,
+    /// The callback called when the selection changes.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_change: Callback<Option<T>>,
+    /// The callback called when the selection loses focus.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_blur: Callback<()>,
+    /// The name of the field within a surrounding form.
+    #[prop_or_default]
+    pub name: String,
+    /// The ungrouped options to choose from.
+    #[prop_or_default]
+    pub options: Vec<T>,
+    /// Groups of options to choose from, displayed after the ungrouped
+    /// options. Grouped options follow the ungrouped options when counting
+    /// option indices.
+    #[prop_or_default]
+    pub groups: Vec<SelectOptionGroup<T>>,
+    /// A source to load options from in place of `options` and `groups`. The
+    /// search query is passed to the source when the options are filterable.
+    #[prop_or_default]
+    pub source: Option<SharedOptionSource<T>>,
+    /// The time in milliseconds to wait after the search query changes
+    /// before loading options from the source.
+    #[prop_or(300)]
+    pub debounce_ms: u32,
+    /// A callback rendering an option. Option labels are displayed if this is
+    /// not provided.
+    #[prop_or_default]
+    pub render: Option<Callback<T, Html>>,
     /// The selection label.
     #[prop_or_default]
     pub label: String,
@@ -156,127 +972,110 @@ pub struct SelectWithNullProps {
     /// Whether the selection is required to be in a non-null state.
     #[prop_or(false)]
     pub required: bool,
+    /// Whether to mark the selection as optional when it is not required.
+    #[prop_or(false)]
+    pub show_optional: bool,
+    /// Help text describing the selection.
+    #[prop_or_default]
+    pub help: String,
     /// An optional error message.
     #[prop_or_default]
     pub error: Option<String>,
     /// Whether the selection is disabled.
     #[prop_or(false)]
     pub disabled: bool,
-    /// Child elements.
-    pub children: ChildrenWithProps<SelectOption>,
+    /// Whether the options can be narrowed down with a search field in the
+    /// dropdown.
+    #[prop_or(false)]
+    pub filterable: bool,
+    /// Search field placeholder text.
+    #[prop_or("Search...".to_owned())]
+    pub search_placeholder: String,
+    /// The heights of the rows in the dropdown. Only the rows scrolled into
+    /// view are rendered if this is set, keeping long lists of options
+    /// responsive.
+    #[prop_or_default]
+    pub row_height: Option<RowHeight>,
+    /// Whether the selection can be returned to the null option with an
+    /// inline clear button or the Backspace key.
+    #[prop_or(false)]
+    pub clearable: bool,
+    /// The callback called when the selection is cleared.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_clear: Callback<()>,
+    /// The callback called with the search query when the user chooses to
+    /// create a new option from it. A row offering to create the option is
+    /// displayed when the query matches no option exactly. The dropdown is
+    /// closed, and the callback is responsible for adding the option, which
+    /// may happen asynchronously, and selecting it. Only used when the
+    /// options are filterable.
+    #[prop_or_default]
+    pub on_create: Option<Callback<String>>,
 }
 
 /// A select component with a null option.
 #[function_component]
-pub fn SelectWithNull(props: &SelectWithNullProps) -> Html {
+pub fn SelectWithNull<T: SelectItem + PartialEq + Clone + 'static>(
+    props: &SelectWithNullProps<T>,
+) -> Html {
     let SelectWithNullProps {
         state,
+        value,
+        on_change,
+        on_blur,
+        name,
+        options,
+        groups,
+        source,
+        debounce_ms,
+        render,
         label,
         null_label,
         required,
+        show_optional,
+        help,
         error,
         disabled,
-        children,
+        filterable,
+        search_placeholder,
+        row_height,
+        clearable,
+        on_clear,
+        on_create,
     } = props.clone();
 
-    let id_state = use_state(new_id);
-    let id = (*id_state).clone();
-    let dropdown_open = use_state(|| false);
-
-    let on_button_click = {
-        let dropdown_open_local = dropdown_open.clone();
-        move |_| {
-            if !disabled {
-                dropdown_open_local.set(!*dropdown_open_local);
-            }
-        }
-    };
+    let state = use_value_handle(state, value, on_change);
 
-    let select_node = use_node_ref();
-    use_click_away(select_node.clone(), {
-        let dropdown_open_local = dropdown_open.clone();
-        move |_| {
-            dropdown_open_local.set(false);
-        }
+    let selected = (*state).clone();
+    let on_select = Callback::from(move |option: Option<T>| {
+        state.set(option);
     });
 
-    let selected_child = if let Some(state_value) = *state {
-        if state_value < children.len() {
-            children.iter().nth(state_value).unwrap().into()
-        } else {
-            html! {
-                <SelectOption>{null_label.clone()}</SelectOption>
-            }
-        }
-    } else {
-        html! {
-            <SelectOption>{null_label.clone()}</SelectOption>
-        }
-    };
-
-    let new_children = children
-        .iter()
-        .enumerate()
-        .map(|(index, child)| {
-            let SelectOptionProps {
-                disabled: child_disabled,
-                children: child_children,
-            } = (*child.props).clone();
-
-            let child_state = state.clone();
-            let child_dropdown_open = dropdown_open.clone();
-            let on_child_click = move |_| {
-                if !child_disabled {
-                    child_state.set(Some(index));
-                    child_dropdown_open.set(false);
-                }
-            };
-
-            html! {
-                <div onclick={on_child_click} class={classes!("base-select-option", child_disabled.then_some("base-select-option-disabled"))}>
-                    {child_children}
-                </div>
-            }
-        })
-        .collect::<Html>();
-
-    let on_null_click = {
-        let null_state = state; // .clone();
-        let null_dropdown_open = dropdown_open.clone();
-        move |_| {
-            null_state.set(None);
-            null_dropdown_open.set(false);
-        }
-    };
-
     html! {
-        <div class={classes!("base-select-container", disabled.then_some("base-select-container-disabled"), (*dropdown_open).then_some("base-select-container-open"))}>
-            <label for={id.clone()} class="base-select-label">
-                {label}
-                <span class="base-required-mark">{required.then_some(" *").unwrap_or_default()}</span>
-            </label>
-            <div ref={select_node} class="base-select">
-                <button
-                    {id}
-                    onclick={on_button_click}
-                    {disabled}
-                    class={classes!("base-select-button", error.clone().map(|_| "base-select-button-invalid"))}
-                >
-                    <div class="base-select-button-selection">
-                        {selected_child}
-                    </div>
-                    <Icon name="angle-down-solid" {disabled} class="base-select-button-icon" />
-                </button>
-                <div class="base-select-dropdown">
-                    <div class="base-select-popup">
-                        <div onclick={on_null_click} class="base-select-option">
-                            <SelectOption>{null_label}</SelectOption>
-                        </div>
-                        {new_children}
-                    </div>
-                </div>
-            </div>
-            <Error message={error} size={ErrorSize::Small} />
-        </div>
+        <SelectBase<T>
+            {selected}
+            {on_select}
+            {on_blur}
+            {name}
+            {options}
+            {groups}
+            {source}
+            {debounce_ms}
+            {render}
+            {label}
+            null_label={Some(null_label)}
+            {required}
+            {show_optional}
+            {help}
+            {error}
+            {disabled}
+            {filterable}
+            {search_placeholder}
+            {row_height}
+            {clearable}
+            cleared={None::<T>}
+            {on_clear}
+            {on_create}
+        />
     }
 }
@@ -6,7 +6,17 @@ use yew::prelude::*;
 #[derive(Properties, PartialEq, Clone)]
 pub struct SliderProps<N: Number> {
     /// The slider state.
-    pub state: UseStateHandle<N>,
+    #[prop_or_default]
+    pub state: Option<UseStateHandle<N>>,
+    /// The slider value. Used along with `on_change` in place of `state`.
+    #[prop_or_default]
+    pub value: Option<N>,
+    /// The callback called when the slider value changes.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_change: Callback<N>,
+    /// The callback called when the slider loses focus.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_blur: Callback<()>,
     /// The slider label.
     #[prop_or_default]
     pub label: String,
@@ -19,6 +29,12 @@ pub struct SliderProps<N: Number> {
     /// The step size.
     #[prop_or(N::NUMBER_STEP)]
     pub step: N,
+    /// Help text describing the slider.
+    #[prop_or_default]
+    pub help: String,
+    /// An optional error message.
+    #[prop_or_default]
+    pub error: Option<String>,
     /// Whether the slider is disabled.
     #[prop_or(false)]
     pub disabled: bool,
@@ -29,13 +45,20 @@ pub struct SliderProps<N: Number> {
 pub fn Slider<N: Number + 'static>(props: &SliderProps<N>) -> Html {
     let SliderProps {
         state,
+        value,
+        on_change,
+        on_blur,
         label,
         min,
         max,
         step,
+        help,
+        error,
         disabled,
     } = props.clone();
 
+    let state = use_value_handle(state, value, on_change);
+
     let id_state = use_state(new_id);
     let id = (*id_state).clone();
     let value = *state;
@@ -51,10 +74,20 @@ pub fn Slider<N: Number + 'static>(props: &SliderProps<N>) -> Html {
             .unwrap();
         state.set(value);
     };
+    let onblur = move |_| {
+        on_blur.emit(());
+    };
 
     html! {
-        <div class={classes!("base-slider-container", disabled.then_some("base-slider-disabled"))}>
-            <label for={id.clone()} class="base-slider-label">{label}</label>
+        <FieldWrapper
+            id={id.clone()}
+            {label}
+            {help}
+            {error}
+            {disabled}
+            class={classes!("base-slider-container", disabled.then_some("base-slider-disabled"))}
+            label_class="base-slider-label"
+        >
             <div class="base-slider">
                 <div class="base-slider-track">
                     <ProgressBar {progress} {disabled} />
@@ -70,10 +103,11 @@ pub fn Slider<N: Number + 'static>(props: &SliderProps<N>) -> Html {
                     max={max.to_string()}
                     step={step.to_string()}
                     {oninput}
+                    {onblur}
                     {disabled}
                     class="base-slider-input"
                 />
             </div>
-        </div>
+        </FieldWrapper>
     }
 }
@@ -1,3 +1,4 @@
+use super::*;
 use crate::util::*;
 use yew::prelude::*;
 
@@ -5,10 +6,29 @@ use yew::prelude::*;
 #[derive(Properties, PartialEq, Clone)]
 pub struct SwitchProps {
     /// The switch state.
-    pub state: UseStateHandle<bool>,
+    #[prop_or_default]
+    pub state: Option<UseStateHandle<bool>>,
+    /// The switch value. Used along with `on_change` in place of `state`.
+    #[prop_or_default]
+    pub value: Option<bool>,
+    /// The callback called when the switch value changes.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_change: Callback<bool>,
+    /// The callback called when the switch loses focus.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_blur: Callback<()>,
+    /// The name of the field within a surrounding form.
+    #[prop_or_default]
+    pub name: String,
     /// The switch label.
     #[prop_or_default]
     pub label: String,
+    /// Help text describing the switch.
+    #[prop_or_default]
+    pub help: String,
+    /// An optional error message.
+    #[prop_or_default]
+    pub error: Option<String>,
     /// Whether the switch is disabled.
     #[prop_or(false)]
     pub disabled: bool,
@@ -19,29 +39,61 @@ pub struct SwitchProps {
 pub fn Switch(props: &SwitchProps) -> Html {
     let SwitchProps {
         state,
+        value,
+        on_change,
+        on_blur,
+        name,
         label,
+        help,
+        error,
         disabled,
     } = props.clone();
 
+    let state = use_value_handle(state, value, on_change);
+
     let checked = *state;
+    let input_node = use_node_ref();
+    let form_error = use_form_registration(
+        &name,
+        FormField {
+            label: label.clone(),
+            node: input_node.clone(),
+            ..FormField::new(FormValue::Bool(checked), false, error.clone())
+        },
+    );
+    use_form_restore(&name, {
+        let state = state.clone();
+        move |value| {
+            if let FormValue::Bool(value) = value {
+                state.set(value);
+            }
+        }
+    });
     let onclick = move |event: MouseEvent| {
         let new_value = checkbox_checked(event);
         state.set(new_value);
     };
+    let onblur = move |_| {
+        on_blur.emit(());
+    };
+    let error = error.or(form_error);
 
     html! {
-        <div class="base-switch-container">
+        <FieldWrapper {help} {error} {disabled} class="base-switch-container">
             <label class={classes!("base-switch", disabled.then_some("base-switch-disabled"))}>
                 <span class="base-switch-label">{label}</span>
                 <input
+                    ref={input_node}
                     type="checkbox"
+                    {name}
                     {checked}
                     {onclick}
+                    {onblur}
                     {disabled}
                     class="base-switch-input"
                 />
                 <span class="base-switch-toggle"></span>
             </label>
-        </div>
+        </FieldWrapper>
     }
 }
@@ -33,7 +33,20 @@ impl TextAreaResize {
 #[derive(Properties, PartialEq, Clone)]
 pub struct TextAreaProps {
     /// The textarea state.
-    pub state: UseStateHandle<String>,
+    #[prop_or_default]
+    pub state: Option<UseStateHandle<String>>,
+    /// The textarea value. Used along with `on_change` in place of `state`.
+    #[prop_or_default]
+    pub value: Option<String>,
+    /// The callback called when the textarea value changes.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_change: Callback<String>,
+    /// The callback called when the textarea loses focus.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_blur: Callback<()>,
+    /// The name of the field within a surrounding form.
+    #[prop_or_default]
+    pub name: String,
     /// The textarea label.
     #[prop_or_default]
     pub label: String,
@@ -43,15 +56,38 @@ pub struct TextAreaProps {
     /// The maximum number of characters allowed.
     #[prop_or(524288)]
     pub max_length: usize,
+    /// Whether to show a character counter below the textarea.
+    #[prop_or(false)]
+    pub show_counter: bool,
     /// Whether the textarea is required to be filled out.
     #[prop_or(false)]
     pub required: bool,
+    /// Whether to mark the textarea as optional when it is not required.
+    #[prop_or(false)]
+    pub show_optional: bool,
+    /// Help text describing the textarea.
+    #[prop_or_default]
+    pub help: String,
     /// In what way the textarea can be resized.
     #[prop_or_default]
     pub resize: TextAreaResize,
     /// An optional error message.
     #[prop_or_default]
     pub error: Option<String>,
+    /// Validators applied to the textarea value.
+    #[prop_or_default]
+    pub validators: Validators<str>,
+    /// When validation errors are displayed.
+    #[prop_or_default]
+    pub validation_mode: ValidationMode,
+    /// Asynchronous validators applied to the textarea value. These are only
+    /// run once all other validation passes.
+    #[prop_or_default]
+    pub async_validators: AsyncValidators<str>,
+    /// The time in milliseconds to wait after the value changes before
+    /// running asynchronous validators.
+    #[prop_or(300)]
+    pub async_debounce_ms: u32,
     /// Whether the textarea is disabled.
     #[prop_or(false)]
     pub disabled: bool,
@@ -62,42 +98,110 @@ pub struct TextAreaProps {
 pub fn TextArea(props: &TextAreaProps) -> Html {
     let TextAreaProps {
         state,
+        value,
+        on_change,
+        on_blur,
+        name,
         label,
         placeholder,
         max_length,
+        show_counter,
         required,
+        show_optional,
+        help,
         resize,
         error,
+        validators,
+        validation_mode,
+        async_validators,
+        async_debounce_ms,
         disabled,
     } = props.clone();
 
+    let state = use_value_handle(state, value, on_change);
+
     let value = (*state).clone();
+    let textarea_node = use_node_ref();
+    let validation = use_validation(validation_mode);
+    let validation_error = validators.validate(&value);
+    let async_validation = use_async_validation(
+        value.clone(),
+        validation_error.is_none() && !async_validators.is_empty(),
+        async_debounce_ms,
+        move |value: &String| async_validators.validate(value),
+    );
+    let validation_error = validation_error.or(async_validation.error);
+    let pending = async_validation.pending;
+    let form_error = use_form_registration(
+        &name,
+        FormField {
+            label: label.clone(),
+            node: textarea_node.clone(),
+            pending,
+            error_shown: error.is_some() || validation.visible(),
+            ..FormField::new(
+                FormValue::Text(value.clone()),
+                required,
+                error.clone().or_else(|| validation_error.clone()),
+            )
+        },
+    );
+    use_form_restore(&name, {
+        let state = state.clone();
+        move |value| {
+            if let FormValue::Text(value) = value {
+                state.set(value);
+            }
+        }
+    });
+    let error = error
+        .or_else(|| validation.visible_error(validation_error))
+        .or(form_error);
     let id_state = use_state(new_id);
     let id = (*id_state).clone();
     let resize_class = format!("base-textarea-resize-{}", resize.resize_option_name());
-    let oninput = move |event: InputEvent| {
-        let new_value = textarea_event_value(event);
-        state.set(new_value);
+    let oninput = {
+        let validation = validation.clone();
+        move |event: InputEvent| {
+            let new_value = textarea_event_value(event);
+            state.set(new_value);
+            validation.changed();
+        }
+    };
+    let onblur = move |_| {
+        validation.blurred();
+        on_blur.emit(());
     };
 
     html! {
-        <div class={classes!("base-textarea-container", disabled.then_some("base-textarea-container-disabled"))}>
-            <label for={id.clone()} class="base-textarea-label">
-                {label}
-                <span class="base-required-mark">{required.then_some(" *").unwrap_or_default()}</span>
-            </label>
+        <FieldWrapper
+            id={id.clone()}
+            {label}
+            {required}
+            {show_optional}
+            {help}
+            max_length={show_counter.then_some(max_length)}
+            length={value.chars().count()}
+            {pending}
+            error={error.clone()}
+            {disabled}
+            class={classes!("base-textarea-container", disabled.then_some("base-textarea-container-disabled"))}
+            label_class="base-textarea-label"
+        >
             <textarea
+                ref={textarea_node}
                 rows={3}
-                {value}
+                value={value.clone()}
                 {id}
+                {name}
                 {oninput}
+                {onblur}
                 {placeholder}
                 {required}
                 {disabled}
                 maxlength={max_length.to_string()}
-                class={classes!("base-textarea", resize_class, error.clone().map(|_| "base-textarea-invalid"))}
+                class={classes!("base-textarea", resize_class, error.map(|_| "base-textarea-invalid"))}
             />
-            <Error message={error} size={ErrorSize::Small} />
-        </div>
+        </FieldWrapper>
     }
 }
@@ -0,0 +1,806 @@
+use crate::util::*;
+use gloo_timers::callback::Timeout;
+use regex::Regex;
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use yew::platform::spawn_local;
+use yew::prelude::*;
+
+/// A trait for validating field values.
+pub trait Validator<T: ?Sized> {
+    /// Validates the value, returning an error message if it is invalid.
+    fn validate(&self, value: &T) -> Result<(), String>;
+}
+
+impl<T: ?Sized, F: Fn(&T) -> Result<(), String>> Validator<T> for F {
+    fn validate(&self, value: &T) -> Result<(), String> {
+        self(value)
+    }
+}
+
+/// Checks whether a string looks like an email address.
+fn is_email(value: &str) -> bool {
+    match value.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.contains('.')
+                && domain.split('.').all(|part| !part.is_empty())
+                && !value.chars().any(char::is_whitespace)
+        }
+        None => false,
+    }
+}
+
+/// Checks whether a string looks like an absolute URL.
+fn is_url(value: &str) -> bool {
+    match value.split_once("://") {
+        Some((scheme, rest)) => {
+            let host = rest.split(['/', '?', '#']).next().unwrap_or_default();
+
+            !scheme.is_empty()
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.')
+                && !host.is_empty()
+                && !value.chars().any(char::is_whitespace)
+        }
+        None => false,
+    }
+}
+
+/// Validates that a value is an email address. Empty values are considered
+/// valid; use `required` to require a value.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EmailValidator;
+
+impl Validator<str> for EmailValidator {
+    fn validate(&self, value: &str) -> Result<(), String> {
+        if value.is_empty() || is_email(value) {
+            Ok(())
+        } else {
+            Err("Invalid email address".to_owned())
+        }
+    }
+}
+
+/// Validates that a value is an absolute URL. Empty values are considered
+/// valid; use `required` to require a value.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct UrlValidator;
+
+impl Validator<str> for UrlValidator {
+    fn validate(&self, value: &str) -> Result<(), String> {
+        if value.is_empty() || is_url(value) {
+            Ok(())
+        } else {
+            Err("Invalid URL".to_owned())
+        }
+    }
+}
+
+/// Validates that a value matches a regular expression. Empty values are
+/// considered valid; use `required` to require a value.
+#[derive(Debug, Clone)]
+pub struct RegexValidator {
+    /// The regular expression to match.
+    regex: Regex,
+    /// The error message used when the value does not match.
+    message: String,
+}
+
+impl RegexValidator {
+    /// Creates a new regular expression validator, failing if the pattern is
+    /// not a valid regular expression.
+    pub fn new(pattern: &str, message: &str) -> Result<Self, regex::Error> {
+        Ok(Self::from_regex(Regex::new(pattern)?, message))
+    }
+
+    /// Creates a new regular expression validator from an already compiled
+    /// regular expression.
+    pub fn from_regex(regex: Regex, message: &str) -> Self {
+        Self {
+            regex,
+            message: message.to_owned(),
+        }
+    }
+}
+
+impl Validator<str> for RegexValidator {
+    fn validate(&self, value: &str) -> Result<(), String> {
+        if value.is_empty() || self.regex.is_match(value) {
+            Ok(())
+        } else {
+            Err(self.message.clone())
+        }
+    }
+}
+
+/// Validates that a value has at least a minimum number of characters. Empty
+/// values are considered valid; use `required` to require a value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MinLengthValidator(pub usize);
+
+impl Validator<str> for MinLengthValidator {
+    fn validate(&self, value: &str) -> Result<(), String> {
+        if value.is_empty() || value.chars().count() >= self.0 {
+            Ok(())
+        } else {
+            Err(format!("Must be at least {} characters", self.0))
+        }
+    }
+}
+
+/// Validates that a value has at most a maximum number of characters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MaxLengthValidator(pub usize);
+
+impl Validator<str> for MaxLengthValidator {
+    fn validate(&self, value: &str) -> Result<(), String> {
+        if value.chars().count() <= self.0 {
+            Ok(())
+        } else {
+            Err(format!("Must be at most {} characters", self.0))
+        }
+    }
+}
+
+/// Validates that a number falls within an inclusive range.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RangeValidator<N: Number> {
+    /// The minimum value.
+    pub min: N,
+    /// The maximum value.
+    pub max: N,
+}
+
+impl<N: Number> RangeValidator<N> {
+    /// Creates a new range validator.
+    pub fn new(min: N, max: N) -> Self {
+        Self { min, max }
+    }
+}
+
+impl<N: Number> Validator<N> for RangeValidator<N> {
+    fn validate(&self, value: &N) -> Result<(), String> {
+        if self.min <= *value && *value <= self.max {
+            Ok(())
+        } else {
+            Err(format!("Must be between {} and {}", self.min, self.max))
+        }
+    }
+}
+
+/// A list of validators to apply to a field value.
+pub struct Validators<T: ?Sized>(Vec<Rc<dyn Validator<T>>>);
+
+impl<T: ?Sized> Validators<T> {
+    /// Creates an empty list of validators.
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Adds a validator to the list.
+    pub fn with(mut self, validator: impl Validator<T> + 'static) -> Self {
+        self.0.push(Rc::new(validator));
+        self
+    }
+
+    /// Whether the list contains no validators.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Runs each validator in order, returning the first error message.
+    pub fn validate(&self, value: &T) -> Option<String> {
+        self.0
+            .iter()
+            .find_map(|validator| validator.validate(value).err())
+    }
+}
+
+impl<T: ?Sized> Clone for Validators<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: ?Sized> Default for Validators<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ?Sized> PartialEq for Validators<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.len() == other.0.len()
+            && self
+                .0
+                .iter()
+                .zip(other.0.iter())
+                .all(|(a, b)| Rc::ptr_eq(a, b))
+    }
+}
+
+/// When validation errors are displayed.
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ValidationMode {
+    /// Errors are displayed once the value has been changed.
+    #[default]
+    OnChange,
+    /// Errors are displayed once the field has lost focus.
+    OnBlur,
+    /// Errors are displayed once the surrounding form has been submitted.
+    OnSubmit,
+}
+
+/// A handle to a field's validation display state.
+#[derive(Clone)]
+pub(crate) struct UseValidationHandle {
+    /// The validation mode.
+    mode: ValidationMode,
+    /// Whether the value has been changed.
+    changed: UseStateHandle<bool>,
+    /// Whether the field has lost focus.
+    blurred: UseStateHandle<bool>,
+}
+
+impl UseValidationHandle {
+    /// Records that the value has been changed.
+    pub fn changed(&self) {
+        if !*self.changed {
+            self.changed.set(true);
+        }
+    }
+
+    /// Records that the field has lost focus.
+    pub fn blurred(&self) {
+        if !*self.blurred {
+            self.blurred.set(true);
+        }
+    }
+
+    /// Whether validation errors should currently be displayed. Errors in
+    /// `OnSubmit` mode are displayed by the surrounding form.
+    pub fn visible(&self) -> bool {
+        match self.mode {
+            ValidationMode::OnChange => *self.changed || *self.blurred,
+            ValidationMode::OnBlur => *self.blurred,
+            ValidationMode::OnSubmit => false,
+        }
+    }
+
+    /// Filters a validation error down to what should currently be displayed.
+    pub fn visible_error(&self, error: Option<String>) -> Option<String> {
+        error.filter(|_| self.visible())
+    }
+}
+
+/// Tracks when a field's validation errors should be displayed.
+#[hook]
+pub(crate) fn use_validation(mode: ValidationMode) -> UseValidationHandle {
+    let changed = use_state(|| false);
+    let blurred = use_state(|| false);
+
+    UseValidationHandle {
+        mode,
+        changed,
+        blurred,
+    }
+}
+
+/// A future resolving to the result of an asynchronous validation.
+pub type ValidationFuture = Pin<Box<dyn Future<Output = Result<(), String>>>>;
+
+/// A trait for validating field values asynchronously, e.g. when a check
+/// requires a round trip to a server.
+pub trait AsyncValidator<T: ?Sized> {
+    /// Validates the value, resolving to an error message if it is invalid.
+    fn validate(&self, value: &T) -> ValidationFuture;
+}
+
+impl<T, F, Fut> AsyncValidator<T> for F
+where
+    T: ?Sized,
+    F: Fn(&T) -> Fut,
+    Fut: Future<Output = Result<(), String>> + 'static,
+{
+    fn validate(&self, value: &T) -> ValidationFuture {
+        Box::pin(self(value))
+    }
+}
+
+/// A list of asynchronous validators to apply to a field value.
+pub struct AsyncValidators<T: ?Sized>(Vec<Rc<dyn AsyncValidator<T>>>);
+
+impl<T: ?Sized> AsyncValidators<T> {
+    /// Creates an empty list of asynchronous validators.
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Adds an asynchronous validator to the list.
+    pub fn with(mut self, validator: impl AsyncValidator<T> + 'static) -> Self {
+        self.0.push(Rc::new(validator));
+        self
+    }
+
+    /// Whether the list contains no validators.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Starts each validator, resolving to the first error message once they
+    /// have completed in order.
+    pub fn validate(&self, value: &T) -> ValidationFuture {
+        let futures = self
+            .0
+            .iter()
+            .map(|validator| validator.validate(value))
+            .collect::<Vec<_>>();
+
+        Box::pin(async move {
+            for future in futures {
+                future.await?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+impl<T: ?Sized> Clone for AsyncValidators<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: ?Sized> Default for AsyncValidators<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ?Sized> PartialEq for AsyncValidators<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.len() == other.0.len()
+            && self
+                .0
+                .iter()
+                .zip(other.0.iter())
+                .all(|(a, b)| Rc::ptr_eq(a, b))
+    }
+}
+
+/// The state of a field's asynchronous validation.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct AsyncValidationState {
+    /// Whether a validation is currently pending.
+    pub pending: bool,
+    /// The error message from the most recent completed validation.
+    pub error: Option<String>,
+}
+
+/// Tracks the generations of asynchronous validations, so that the results of
+/// validations which have been superseded or cancelled are discarded. A new
+/// generation starts whenever the validated value changes, including while
+/// the previous validation is still being debounced.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct AsyncValidationTracker {
+    /// The current generation.
+    generation: usize,
+}
+
+impl AsyncValidationTracker {
+    /// Starts a new generation for a changed value, returning the generation
+    /// along with the state to display while it is validated.
+    pub fn start(&mut self, enabled: bool) -> (usize, AsyncValidationState) {
+        self.generation += 1;
+
+        let state = AsyncValidationState {
+            pending: enabled,
+            error: None,
+        };
+
+        (self.generation, state)
+    }
+
+    /// Cancels the current generation, so that its result is discarded.
+    pub fn cancel(&mut self) {
+        self.generation += 1;
+    }
+
+    /// Gets the state to display once the validation of the given generation
+    /// completes, or `None` if the result is stale and should be ignored.
+    pub fn complete(
+        &self,
+        generation: usize,
+        result: Result<(), String>,
+    ) -> Option<AsyncValidationState> {
+        (generation == self.generation).then(|| AsyncValidationState {
+            pending: false,
+            error: result.err(),
+        })
+    }
+}
+
+/// Runs an asynchronous validation against a value. Validation is debounced,
+/// and the result of a validation is discarded if the value has since
+/// changed. Nothing is run while `enabled` is false.
+#[hook]
+pub(crate) fn use_async_validation<T, F>(
+    value: T,
+    enabled: bool,
+    debounce_ms: u32,
+    validate: F,
+) -> AsyncValidationState
+where
+    T: PartialEq + 'static,
+    F: FnOnce(&T) -> ValidationFuture + 'static,
+{
+    let state = use_state(AsyncValidationState::default);
+    let tracker = use_mut_ref(AsyncValidationTracker::default);
+    let timeout = use_mut_ref(|| Option::<Timeout>::None);
+
+    {
+        let state = state.clone();
+        use_effect_with_deps(
+            move |(value, enabled): &(Rc<T>, bool)| {
+                let (generation, started) = tracker.borrow_mut().start(*enabled);
+                state.set(started);
+
+                if *enabled {
+                    let value = value.clone();
+                    let timeout_tracker = tracker.clone();
+                    *timeout.borrow_mut() = Some(Timeout::new(debounce_ms, move || {
+                        let future = validate(&value);
+
+                        spawn_local(async move {
+                            let result = future.await;
+
+                            if let Some(completed) =
+                                timeout_tracker.borrow().complete(generation, result)
+                            {
+                                state.set(completed);
+                            }
+                        });
+                    }));
+                }
+
+                cancel_async_validation(tracker, timeout)
+            },
+            (Rc::new(value), enabled),
+        );
+    }
+
+    (*state).clone()
+}
+
+/// Returns a teardown function that cancels any debounced validation and
+/// discards the result of any validation already in flight.
+fn cancel_async_validation(
+    tracker: Rc<RefCell<AsyncValidationTracker>>,
+    timeout: Rc<RefCell<Option<Timeout>>>,
+) -> impl FnOnce() {
+    move || {
+        tracker.borrow_mut().cancel();
+        timeout.borrow_mut().take(); // timeout is cancelled when dropped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::future::{pending, ready};
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+
+    #[test]
+    fn email_validator() {
+        assert_eq!(EmailValidator.validate(""), Ok(()));
+        assert_eq!(EmailValidator.validate("ferris@example.com"), Ok(()));
+        assert_eq!(EmailValidator.validate("fërris@exämple.com"), Ok(()));
+
+        for invalid in [
+            "ferris",
+            "@example.com",
+            "ferris@",
+            "ferris@example",
+            "ferris@example.",
+            "ferris@@example.com",
+            "fer ris@example.com",
+        ] {
+            assert_eq!(
+                EmailValidator.validate(invalid),
+                Err("Invalid email address".to_owned()),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn url_validator() {
+        assert_eq!(UrlValidator.validate(""), Ok(()));
+        assert_eq!(UrlValidator.validate("https://example.com"), Ok(()));
+        assert_eq!(UrlValidator.validate("git+ssh://example.com/repo"), Ok(()));
+        assert_eq!(UrlValidator.validate("https://例え.jp/パス"), Ok(()));
+
+        for invalid in [
+            "example.com",
+            "://example.com",
+            "https://",
+            "https:///path",
+            "ht tp://example.com",
+            "https://example.com/a b",
+        ] {
+            assert_eq!(
+                UrlValidator.validate(invalid),
+                Err("Invalid URL".to_owned()),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn min_length_validator() {
+        let validator = MinLengthValidator(3);
+
+        assert_eq!(validator.validate(""), Ok(()));
+        assert_eq!(
+            validator.validate("ab"),
+            Err("Must be at least 3 characters".to_owned())
+        );
+        assert_eq!(validator.validate("abc"), Ok(()));
+        assert_eq!(validator.validate("日本語"), Ok(()));
+        assert_eq!(
+            validator.validate("日本"),
+            Err("Must be at least 3 characters".to_owned())
+        );
+    }
+
+    #[test]
+    fn max_length_validator() {
+        let validator = MaxLengthValidator(3);
+
+        assert_eq!(validator.validate(""), Ok(()));
+        assert_eq!(validator.validate("abc"), Ok(()));
+        assert_eq!(
+            validator.validate("abcd"),
+            Err("Must be at most 3 characters".to_owned())
+        );
+        assert_eq!(validator.validate("日本語"), Ok(()));
+        assert_eq!(
+            validator.validate("日本語だ"),
+            Err("Must be at most 3 characters".to_owned())
+        );
+        assert_eq!(
+            MaxLengthValidator(0).validate("a"),
+            Err("Must be at most 0 characters".to_owned())
+        );
+    }
+
+    #[test]
+    fn range_validator() {
+        let validator = RangeValidator::new(1, 10);
+
+        assert_eq!(validator.validate(&1), Ok(()));
+        assert_eq!(validator.validate(&10), Ok(()));
+        assert_eq!(
+            validator.validate(&0),
+            Err("Must be between 1 and 10".to_owned())
+        );
+        assert_eq!(
+            validator.validate(&11),
+            Err("Must be between 1 and 10".to_owned())
+        );
+
+        let validator = RangeValidator::new(-0.5, 0.5);
+        assert_eq!(validator.validate(&0.5), Ok(()));
+        assert_eq!(
+            validator.validate(&-0.6),
+            Err("Must be between -0.5 and 0.5".to_owned())
+        );
+        assert!(validator.validate(&f64::NAN).is_err());
+    }
+
+    #[test]
+    fn regex_validator() {
+        let validator = RegexValidator::new("^[a-z]+$", "Lowercase letters only").unwrap();
+
+        assert_eq!(validator.validate(""), Ok(()));
+        assert_eq!(validator.validate("ferris"), Ok(()));
+        assert_eq!(
+            validator.validate("Ferris"),
+            Err("Lowercase letters only".to_owned())
+        );
+
+        let validator =
+            RegexValidator::from_regex(Regex::new(r"^\p{L}+$").unwrap(), "Letters only");
+        assert_eq!(validator.validate("ñandú"), Ok(()));
+        assert_eq!(validator.validate("ñandú1"), Err("Letters only".to_owned()));
+    }
+
+    #[test]
+    fn regex_validator_rejects_invalid_pattern() {
+        assert!(RegexValidator::new("[a-z", "Invalid").is_err());
+    }
+
+    #[test]
+    fn validators_report_first_error() {
+        let validators = Validators::<str>::new()
+            .with(MinLengthValidator(2))
+            .with(MaxLengthValidator(4));
+
+        assert_eq!(validators.validate("abc"), None);
+        assert_eq!(
+            validators.validate("a"),
+            Some("Must be at least 2 characters".to_owned())
+        );
+        assert_eq!(
+            validators.validate("abcde"),
+            Some("Must be at most 4 characters".to_owned())
+        );
+    }
+
+    /// A waker that does nothing, as nothing waits to be woken.
+    struct NoopWaker;
+
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    /// Polls a future once, without an executor.
+    fn poll_once(future: &mut ValidationFuture) -> Poll<Result<(), String>> {
+        let waker = Waker::from(Arc::new(NoopWaker));
+        future.as_mut().poll(&mut Context::from_waker(&waker))
+    }
+
+    /// A future that is pending until it has been polled a number of times.
+    struct Delayed {
+        polls: usize,
+        result: Result<(), String>,
+    }
+
+    impl Future for Delayed {
+        type Output = Result<(), String>;
+
+        fn poll(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Self::Output> {
+            if self.polls == 0 {
+                Poll::Ready(self.result.clone())
+            } else {
+                self.polls -= 1;
+                Poll::Pending
+            }
+        }
+    }
+
+    #[test]
+    fn async_validators_resolve_when_empty() {
+        let validators = AsyncValidators::<str>::new();
+
+        assert_eq!(poll_once(&mut validators.validate("")), Poll::Ready(Ok(())));
+    }
+
+    #[test]
+    fn async_validators_resolve_to_first_error() {
+        let validators = AsyncValidators::<str>::new()
+            .with(|_: &str| ready(Ok(())))
+            .with(|value: &str| ready(Err(format!("{value} is taken"))))
+            .with(|_: &str| ready(Err("Unreachable".to_owned())));
+
+        assert_eq!(
+            poll_once(&mut validators.validate("ferris")),
+            Poll::Ready(Err("ferris is taken".to_owned()))
+        );
+    }
+
+    #[test]
+    fn async_validators_do_not_wait_after_an_error() {
+        let validators = AsyncValidators::<str>::new()
+            .with(|_: &str| ready(Err("Invalid".to_owned())))
+            .with(|_: &str| pending());
+
+        assert_eq!(
+            poll_once(&mut validators.validate("")),
+            Poll::Ready(Err("Invalid".to_owned()))
+        );
+    }
+
+    #[test]
+    fn async_validators_report_errors_in_order() {
+        let validators = AsyncValidators::<str>::new()
+            .with(|_: &str| pending())
+            .with(|_: &str| ready(Err("Invalid".to_owned())));
+
+        assert_eq!(poll_once(&mut validators.validate("")), Poll::Pending);
+    }
+
+    #[test]
+    fn async_validators_wait_for_pending_validators() {
+        let validators = AsyncValidators::<str>::new()
+            .with(|_: &str| Delayed {
+                polls: 2,
+                result: Ok(()),
+            })
+            .with(|_: &str| Delayed {
+                polls: 1,
+                result: Err("Invalid".to_owned()),
+            });
+        let mut future = validators.validate("");
+
+        assert_eq!(poll_once(&mut future), Poll::Pending);
+        assert_eq!(poll_once(&mut future), Poll::Pending);
+        assert_eq!(poll_once(&mut future), Poll::Pending);
+        assert_eq!(
+            poll_once(&mut future),
+            Poll::Ready(Err("Invalid".to_owned()))
+        );
+    }
+
+    #[test]
+    fn async_validators_start_every_validator_immediately() {
+        let started = Rc::new(Cell::new(0));
+        let validator = {
+            let started = started.clone();
+            move |_: &str| {
+                started.set(started.get() + 1);
+                pending()
+            }
+        };
+        let validators = AsyncValidators::<str>::new()
+            .with(validator.clone())
+            .with(validator);
+
+        let _future = validators.validate("");
+        assert_eq!(started.get(), 2);
+    }
+
+    #[test]
+    fn async_validation_tracker_applies_current_result() {
+        let mut tracker = AsyncValidationTracker::default();
+        let (generation, started) = tracker.start(true);
+
+        assert!(started.pending);
+        assert_eq!(
+            tracker.complete(generation, Err("Invalid".to_owned())),
+            Some(AsyncValidationState {
+                pending: false,
+                error: Some("Invalid".to_owned()),
+            })
+        );
+    }
+
+    #[test]
+    fn async_validation_tracker_ignores_stale_result() {
+        let mut tracker = AsyncValidationTracker::default();
+        let (stale, _) = tracker.start(true);
+        let (current, _) = tracker.start(true);
+
+        assert_eq!(tracker.complete(stale, Err("Invalid".to_owned())), None);
+        assert_eq!(
+            tracker.complete(current, Ok(())),
+            Some(AsyncValidationState::default())
+        );
+    }
+
+    #[test]
+    fn async_validation_tracker_ignores_cancelled_result() {
+        let mut tracker = AsyncValidationTracker::default();
+        let (generation, _) = tracker.start(true);
+        tracker.cancel();
+
+        assert_eq!(tracker.complete(generation, Ok(())), None);
+    }
+
+    #[test]
+    fn async_validation_tracker_is_idle_when_disabled() {
+        let mut tracker = AsyncValidationTracker::default();
+        tracker.start(true);
+
+        assert_eq!(tracker.start(false).1, AsyncValidationState::default());
+    }
+}
@@ -0,0 +1,287 @@
+use std::collections::HashMap;
+use std::ops::Range;
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use web_sys::{Element, HtmlElement};
+use yew::prelude::*;
+
+/// The number of rows rendered beyond each edge of the visible range, so that
+/// scrolling does not reveal blank space before the next render.
+const OVERSCAN_ROWS: usize = 5;
+
+/// The height in pixels assumed for the viewport before it has been
+/// rendered.
+const DEFAULT_VIEWPORT_HEIGHT: f64 = 320.0;
+
+/// The heights of the rows within a virtualized list.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RowHeight {
+    /// Every row has the given height in pixels.
+    Fixed(f64),
+    /// Rows are assumed to have the given height in pixels until they have
+    /// been rendered and measured.
+    Estimated(f64),
+}
+
+impl RowHeight {
+    /// Gets the fixed or estimated height of a row.
+    fn height(&self) -> f64 {
+        match *self {
+            Self::Fixed(height) | Self::Estimated(height) => height,
+        }
+    }
+}
+
+/// Computes the offset of the top of each row, followed by the total height
+/// of the rows.
+fn row_offsets(count: usize, row_height: RowHeight, measured: &HashMap<usize, f64>) -> Vec<f64> {
+    let mut offsets = Vec::with_capacity(count + 1);
+    let mut offset = 0.0;
+    offsets.push(offset);
+
+    for index in 0..count {
+        offset += match row_height {
+            RowHeight::Fixed(height) => height,
+            RowHeight::Estimated(height) => measured.get(&index).copied().unwrap_or(height),
+        };
+        offsets.push(offset);
+    }
+
+    offsets
+}
+
+/// Computes the range of rows overlapping the viewport, including overscan.
+fn visible_range(offsets: &[f64], scroll_top: f64, viewport_height: f64) -> Range<usize> {
+    let count = offsets.len() - 1;
+    let start = offsets[1..].partition_point(|bottom| *bottom <= scroll_top);
+    let end = offsets[..count].partition_point(|top| *top < scroll_top + viewport_height);
+
+    start.saturating_sub(OVERSCAN_ROWS)..(end + OVERSCAN_ROWS).min(count)
+}
+
+/// A handle to a list whose rows may be virtualized, rendering only the rows
+/// scrolled into view.
+#[derive(Clone)]
+pub(crate) struct UseVirtualListHandle {
+    /// The row heights, or `None` if every row is rendered.
+    row_height: Option<RowHeight>,
+    /// The offset of the top of each row, followed by the total height.
+    offsets: Rc<Vec<f64>>,
+    /// The range of rows to render.
+    range: Range<usize>,
+    /// The scrollable element containing the rows.
+    viewport: NodeRef,
+    /// The element directly containing the rendered rows.
+    content: NodeRef,
+    /// The current scroll position of the viewport.
+    scroll_top: UseStateHandle<f64>,
+}
+
+impl UseVirtualListHandle {
+    /// Gets the reference to attach to the scrollable element containing the
+    /// rows.
+    pub fn viewport(&self) -> NodeRef {
+        self.viewport.clone()
+    }
+
+    /// Gets the range of rows to render.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    /// Records the scroll position of the viewport. This should be called
+    /// from the viewport's scroll handler.
+    pub fn on_scroll(&self) {
+        if self.row_height.is_some() {
+            if let Some(viewport) = self.viewport.cast::<Element>() {
+                let scroll_top = f64::from(viewport.scroll_top());
+
+                if *self.scroll_top != scroll_top {
+                    self.scroll_top.set(scroll_top);
+                }
+            }
+        }
+    }
+
+    /// Scrolls the viewport just enough for the row at the given index to be
+    /// fully visible, even if the row is not currently rendered.
+    pub fn scroll_to(&self, index: usize) {
+        if self.row_height.is_none() || index + 1 >= self.offsets.len() {
+            return;
+        }
+
+        if let Some(viewport) = self.viewport.cast::<Element>() {
+            let top = self.offsets[index];
+            let bottom = self.offsets[index + 1];
+            let scroll_top = f64::from(viewport.scroll_top());
+            let viewport_height = f64::from(viewport.client_height());
+
+            if top < scroll_top {
+                viewport.set_scroll_top(top as i32);
+            } else if bottom > scroll_top + viewport_height {
+                viewport.set_scroll_top((bottom - viewport_height).ceil() as i32);
+            }
+        }
+    }
+
+    /// Wraps the rendered rows, reserving space for the rows that are not
+    /// rendered. The rows are returned unchanged if the list is not
+    /// virtualized.
+    pub fn render(&self, rows: Html) -> Html {
+        if self.row_height.is_none() {
+            return rows;
+        }
+
+        let before = self.offsets[self.range.start];
+        let after = self.offsets[self.offsets.len() - 1] - self.offsets[self.range.end];
+
+        html! {
+            <>
+                <div aria-hidden="true" style={format!("height: {before}px")} class="base-virtual-spacer"></div>
+                <div ref={self.content.clone()} role="presentation" class="base-virtual-rows">
+                    {rows}
+                </div>
+                <div aria-hidden="true" style={format!("height: {after}px")} class="base-virtual-spacer"></div>
+            </>
+        }
+    }
+}
+
+/// Creates a handle to a list of rows, rendering only the rows scrolled into
+/// view when row heights are given. Estimated row heights are replaced with
+/// measured heights as rows are rendered. The highlighted row is scrolled into
+/// view whenever it changes, so that keyboard navigation can reach rows that
+/// are not rendered.
+#[hook]
+pub(crate) fn use_virtual_list(
+    count: usize,
+    row_height: Option<RowHeight>,
+    highlighted: Option<usize>,
+) -> UseVirtualListHandle {
+    let viewport = use_node_ref();
+    let content = use_node_ref();
+    let scroll_top = use_state(|| 0.0);
+    let measured = use_mut_ref(HashMap::<usize, f64>::new);
+    let measured_count = use_mut_ref(|| count);
+    let measurements = use_state(|| 0usize);
+
+    // Measurements are tied to row indices, which are meaningless once the
+    // rows change. They are discarded before the offsets are computed, so
+    // that the new rows are never laid out with them.
+    if *measured_count.borrow() != count {
+        *measured_count.borrow_mut() = count;
+        measured.borrow_mut().clear();
+    }
+
+    // Offsets are only recomputed when the rows or their measurements change,
+    // rather than on every scroll.
+    let offsets = {
+        let measured = measured.clone();
+        use_memo(
+            move |(count, row_height, _)| match row_height {
+                Some(row_height) => row_offsets(*count, *row_height, &measured.borrow()),
+                None => Vec::new(),
+            },
+            (count, row_height, *measurements),
+        )
+    };
+    let range = match row_height {
+        Some(row_height) => {
+            // The viewport has no height while the dropdown is hidden.
+            let viewport_height = viewport
+                .cast::<Element>()
+                .map(|viewport| f64::from(viewport.client_height()))
+                .filter(|height| *height >= row_height.height())
+                .unwrap_or(DEFAULT_VIEWPORT_HEIGHT);
+
+            visible_range(&offsets, *scroll_top, viewport_height)
+        }
+        None => 0..count,
+    };
+
+    {
+        let content = content.clone();
+        let measured = measured.clone();
+        let measurements = measurements.clone();
+        let start = range.start;
+        use_effect(move || {
+            if let (Some(RowHeight::Estimated(_)), Some(content)) =
+                (row_height, content.cast::<Element>())
+            {
+                let children = content.children();
+                let mut changed = false;
+
+                for child_index in 0..children.length() {
+                    let height = children
+                        .item(child_index)
+                        .and_then(|child| child.dyn_into::<HtmlElement>().ok())
+                        .map(|child| f64::from(child.offset_height()));
+
+                    if let Some(height) = height {
+                        let index = start + child_index as usize;
+                        let previous = measured.borrow_mut().insert(index, height);
+
+                        changed |=
+                            !previous.is_some_and(|previous| (previous - height).abs() <= 0.5);
+                    }
+                }
+
+                if changed {
+                    measurements.set(*measurements + 1);
+                }
+            }
+        });
+    }
+
+    let handle = UseVirtualListHandle {
+        row_height,
+        offsets,
+        range,
+        viewport,
+        content,
+        scroll_top,
+    };
+
+    {
+        let handle = handle.clone();
+        use_effect_with_deps(
+            move |highlighted| {
+                if let Some(index) = highlighted {
+                    handle.scroll_to(*index);
+                }
+            },
+            highlighted,
+        );
+    }
+
+    handle
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn row_offsets_use_measured_heights() {
+        let measured = HashMap::from([(1, 50.0)]);
+
+        assert_eq!(
+            row_offsets(3, RowHeight::Estimated(20.0), &measured),
+            [0.0, 20.0, 70.0, 90.0]
+        );
+        assert_eq!(
+            row_offsets(3, RowHeight::Fixed(20.0), &measured),
+            [0.0, 20.0, 40.0, 60.0]
+        );
+        assert_eq!(row_offsets(0, RowHeight::Fixed(20.0), &measured), [0.0]);
+    }
+
+    #[test]
+    fn visible_range_includes_overscan() {
+        let offsets = row_offsets(100, RowHeight::Fixed(10.0), &HashMap::new());
+
+        assert_eq!(visible_range(&offsets, 0.0, 50.0), 0..10);
+        assert_eq!(visible_range(&offsets, 500.0, 50.0), 45..60);
+        assert_eq!(visible_range(&offsets, 960.0, 50.0), 91..100);
+    }
+}
@@ -0,0 +1,415 @@
+use super::*;
+use crate::util::*;
+use std::rc::Rc;
+use yew::prelude::*;
+
+/// The status of a step within a wizard.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WizardStepStatus {
+    /// The step has not been reached yet.
+    Upcoming,
+    /// The step is currently displayed.
+    Current,
+    /// The step has been completed.
+    Complete,
+    /// The step was skipped.
+    Skipped,
+}
+
+impl WizardStepStatus {
+    /// Gets the name of the step status.
+    pub fn status_name(&self) -> &'static str {
+        match *self {
+            Self::Upcoming => "upcoming",
+            Self::Current => "current",
+            Self::Complete => "complete",
+            Self::Skipped => "skipped",
+        }
+    }
+}
+
+/// The context provided by a wizard to each of its steps.
+#[derive(Clone, PartialEq)]
+struct WizardStepContext {
+    /// The index of the step.
+    index: usize,
+    /// Whether the step is currently displayed.
+    active: bool,
+    /// The callback called by the step to make its form known to the wizard.
+    on_form: Callback<(usize, FormContext)>,
+}
+
+/// The forms of each step within a wizard.
+#[derive(Clone, Default, PartialEq)]
+struct WizardForms(Vec<Option<FormContext>>);
+
+impl Reducible for WizardForms {
+    type Action = (usize, FormContext);
+
+    fn reduce(self: Rc<Self>, (index, form): Self::Action) -> Rc<Self> {
+        let mut forms = self.0.clone();
+
+        if forms.len() <= index {
+            forms.resize(index + 1, None);
+        }
+
+        forms[index] = Some(form);
+        Rc::new(Self(forms))
+    }
+}
+
+/// Whether a step is optional and none of its fields have been changed, in
+/// which case it is moved past without validating it.
+fn untouched_optional_step(optional: bool, form: Option<&FormState>) -> bool {
+    optional && !form.is_some_and(FormState::is_dirty)
+}
+
+/// Whether the fields of a step allow moving on from it once validated.
+fn step_complete(form: &FormState) -> bool {
+    form.is_valid() && !form.is_pending()
+}
+
+/// Wizard step properties.
+#[derive(Properties, PartialEq, Clone)]
+pub struct WizardStepProps {
+    /// The step title, displayed in the step indicator.
+    pub title: String,
+    /// Whether the step is optional. Optional steps are marked as such in the
+    /// step indicator. While none of an optional step's fields have been
+    /// changed, the next button moves past the step without validating it,
+    /// marking it as skipped. Once a field has been changed, the step is
+    /// validated like any other.
+    #[prop_or(false)]
+    pub optional: bool,
+    /// Whether the step can be skipped without validating its fields.
+    #[prop_or(false)]
+    pub skippable: bool,
+    /// The fields within the step.
+    pub children: Children,
+}
+
+/// A single step within a wizard. Fields within the step register with the
+/// step rather than with any surrounding form.
+#[function_component]
+pub fn WizardStep(props: &WizardStepProps) -> Html {
+    let WizardStepProps { children, .. } = props.clone();
+
+    let form = use_reducer_eq(FormState::default);
+    let context = use_context::<WizardStepContext>();
+    let active = context
+        .as_ref()
+        .map(|context| context.active)
+        .unwrap_or(true);
+
+    use_effect_with_deps(
+        |(context, form)| {
+            if let Some(context) = context {
+                context.on_form.emit((context.index, form.clone()));
+            }
+        },
+        (context, form.clone()),
+    );
+
+    html! {
+        <div hidden={!active} class={classes!("base-wizard-panel", active.then_some("base-wizard-panel-active"))}>
+            <ContextProvider<FormContext> context={form}>
+                {children}
+            </ContextProvider<FormContext>>
+        </div>
+    }
+}
+
+/// Wizard properties.
+#[derive(Properties, PartialEq, Clone)]
+pub struct WizardProps {
+    /// The current step index state.
+    #[prop_or_default]
+    pub state: Option<UseStateHandle<usize>>,
+    /// The current step index. Used along with `on_change` in place of
+    /// `state`.
+    #[prop_or_default]
+    pub value: Option<usize>,
+    /// The callback called when the current step changes.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_change: Callback<usize>,
+    /// The callback called with the aggregated field values of every step
+    /// when the final step is completed.
+    #[prop_or(Callback::from(|_| ()))]
+    pub on_complete: Callback<FormValues>,
+    /// The back button label.
+    #[prop_or("Back".to_owned())]
+    pub back_label: String,
+    /// The next button label.
+    #[prop_or("Next".to_owned())]
+    pub next_label: String,
+    /// The skip button label.
+    #[prop_or("Skip".to_owned())]
+    pub skip_label: String,
+    /// The label of the next button on the final step.
+    #[prop_or("Finish".to_owned())]
+    pub complete_label: String,
+    /// The steps of the wizard.
+    pub children: ChildrenWithProps<WizardStep>,
+}
+
+/// A wizard component, splitting a form into ordered steps. The fields of
+/// the current step are validated before moving on to the next step.
+#[function_component]
+pub fn Wizard(props: &WizardProps) -> Html {
+    let WizardProps {
+        state,
+        value,
+        on_change,
+        on_complete,
+        back_label,
+        next_label,
+        skip_label,
+        complete_label,
+        children,
+    } = props.clone();
+
+    let state = use_value_handle(state, value, on_change);
+
+    let num_steps = children.len();
+    let current = (*state).min(num_steps.saturating_sub(1));
+    let is_last = current + 1 >= num_steps;
+    let forms = use_reducer_eq(WizardForms::default);
+    let skipped = use_state(Vec::<usize>::new);
+    let on_form = {
+        let forms = forms.clone();
+        Callback::from(move |(index, form)| forms.dispatch((index, form)))
+    };
+    let current_form = forms.0.get(current).cloned().flatten();
+    let (current_optional, current_skippable) = children
+        .iter()
+        .nth(current)
+        .map(|child| (child.props.optional, child.props.skippable))
+        .unwrap_or_default();
+
+    let progress = if num_steps > 1 {
+        current as f64 / (num_steps - 1) as f64
+    } else {
+        1.0
+    };
+
+    let go_to = {
+        let state = state.clone();
+        let skipped = skipped.clone();
+        move |index: usize, skip: bool| {
+            let mut new_skipped = (*skipped).clone();
+            new_skipped.retain(|skipped_index| *skipped_index != current);
+
+            if skip {
+                new_skipped.push(current);
+            }
+
+            skipped.set(new_skipped);
+            state.set(index);
+        }
+    };
+
+    let on_back_click = {
+        let state = state.clone();
+        move |_| {
+            if current > 0 {
+                state.set(current - 1);
+            }
+        }
+    };
+    let on_skip_click = {
+        let go_to = go_to.clone();
+        move |_| {
+            if !is_last {
+                go_to(current + 1, true);
+            }
+        }
+    };
+    let on_next_click = {
+        let forms = forms.clone();
+        move |_| {
+            let untouched = untouched_optional_step(current_optional, current_form.as_deref());
+
+            if let Some(form) = current_form.as_ref().filter(|_| !untouched) {
+                form.dispatch(FormAction::Submit);
+
+                if !step_complete(form) {
+                    return;
+                }
+            }
+
+            if is_last {
+                let values = forms
+                    .0
+                    .iter()
+                    .flatten()
+                    .flat_map(|form| form.values())
+                    .collect::<FormValues>();
+                on_complete.emit(values);
+            } else {
+                go_to(current + 1, untouched);
+            }
+        }
+    };
+
+    let step_indicators = children
+        .iter()
+        .enumerate()
+        .map(|(index, child)| {
+            let WizardStepProps {
+                title, optional, ..
+            } = (*child.props).clone();
+
+            let status = if index == current {
+                WizardStepStatus::Current
+            } else if index > current {
+                WizardStepStatus::Upcoming
+            } else if skipped.contains(&index) {
+                WizardStepStatus::Skipped
+            } else {
+                WizardStepStatus::Complete
+            };
+            let status_class = format!("base-wizard-step-{}", status.status_name());
+            let marker = match status {
+                WizardStepStatus::Complete => html! {
+                    <Icon name="check-solid" size={IconSize::Small} class="base-wizard-step-icon" />
+                },
+                WizardStepStatus::Skipped => html! {
+                    <Icon name="forward-solid" size={IconSize::Small} class="base-wizard-step-icon" />
+                },
+                WizardStepStatus::Current | WizardStepStatus::Upcoming => html! {
+                    <span class="base-wizard-step-number">{index + 1}</span>
+                },
+            };
+
+            html! {
+                <li
+                    aria-current={(status == WizardStepStatus::Current).then_some("step")}
+                    class={classes!("base-wizard-step", status_class)}
+                >
+                    <div class="base-wizard-step-marker">{marker}</div>
+                    <div class="base-wizard-step-text">
+                        <span class="base-wizard-step-title">{title}</span>
+                        {optional.then_some(html! {
+                            <span class="base-wizard-step-optional">{"Optional"}</span>
+                        })}
+                    </div>
+                </li>
+            }
+        })
+        .collect::<Html>();
+
+    let steps = children
+        .iter()
+        .enumerate()
+        .map(|(index, child)| {
+            let context = WizardStepContext {
+                index,
+                active: index == current,
+                on_form: on_form.clone(),
+            };
+
+            html! {
+                <ContextProvider<WizardStepContext> {context}>
+                    {child}
+                </ContextProvider<WizardStepContext>>
+            }
+        })
+        .collect::<Html>();
+
+    html! {
+        <div class="base-wizard">
+            <div class="base-wizard-header">
+                <ol class="base-wizard-steps">
+                    {step_indicators}
+                </ol>
+                <ProgressBar {progress} />
+            </div>
+            <div class="base-wizard-body">
+                {steps}
+            </div>
+            <div class="base-wizard-actions">
+                {(current > 0).then_some(html! {
+                    <Button
+                        text={back_label}
+                        style={ButtonStyle::Secondary}
+                        on_click={on_back_click}
+                    />
+                })}
+                <div class="base-wizard-actions-space"></div>
+                {(current_skippable && !is_last).then_some(html! {
+                    <Button
+                        text={skip_label}
+                        style={ButtonStyle::Transparent}
+                        on_click={on_skip_click}
+                    />
+                })}
+                <Button
+                    text={if is_last { complete_label } else { next_label }}
+                    style={ButtonStyle::Primary}
+                    on_click={on_next_click}
+                />
+            </div>
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn form(fields: &[(&str, FormField)]) -> FormState {
+        let state = fields
+            .iter()
+            .fold(Rc::<FormState>::default(), |state, (name, field)| {
+                state.reduce(FormAction::Register((*name).to_owned(), field.clone()))
+            });
+        (*state).clone()
+    }
+
+    fn text_field(value: &str, required: bool, error: Option<&str>) -> FormField {
+        FormField::new(
+            FormValue::Text(value.to_owned()),
+            required,
+            error.map(str::to_owned),
+        )
+    }
+
+    #[test]
+    fn untouched_optional_steps_are_moved_past() {
+        let clean = form(&[("name", text_field("", true, None))]);
+        let dirty = Rc::new(clean.clone()).reduce(FormAction::Register(
+            "name".to_owned(),
+            text_field("Ferris", true, None),
+        ));
+
+        assert!(untouched_optional_step(true, None));
+        assert!(untouched_optional_step(true, Some(&clean)));
+        assert!(!untouched_optional_step(true, Some(&dirty)));
+        assert!(!untouched_optional_step(false, None));
+        assert!(!untouched_optional_step(false, Some(&clean)));
+    }
+
+    #[test]
+    fn steps_are_gated_on_their_fields() {
+        assert!(step_complete(&form(&[])));
+        assert!(step_complete(&form(&[
+            ("name", text_field("Ferris", true, None)),
+            ("nickname", text_field("", false, None)),
+        ])));
+        assert!(!step_complete(&form(&[(
+            "name",
+            text_field("", true, None)
+        )])));
+        assert!(!step_complete(&form(&[(
+            "name",
+            text_field("Ferris", false, Some("Too short"))
+        )])));
+        assert!(!step_complete(&form(&[(
+            "name",
+            FormField {
+                pending: true,
+                ..text_field("Ferris", false, None)
+            }
+        )])));
+    }
+}
@@ -3,4 +3,6 @@
 mod components;
 mod util;
 
+pub use chrono;
 pub use components::*;
+pub use yin_derive::YinForm;
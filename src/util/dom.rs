@@ -1,7 +1,10 @@
 #![allow(dead_code)]
 
 use wasm_bindgen::{JsCast, UnwrapThrowExt};
-use web_sys::{Event, HtmlElement, HtmlInputElement, HtmlTextAreaElement, InputEvent, MouseEvent};
+use web_sys::{
+    Element, Event, FocusEvent, HtmlElement, HtmlInputElement, HtmlTextAreaElement, InputEvent,
+    MouseEvent, Node, ScrollIntoViewOptions, ScrollLogicalPosition,
+};
 use yew::prelude::*;
 
 /// Gets the value of an input element from an event.
@@ -36,13 +39,79 @@ pub fn checkbox_checked(e: MouseEvent) -> bool {
     target.checked()
 }
 
+/// Determines whether a focus event moves focus outside of an element
+/// entirely, rather than to one of its descendants.
+pub fn focus_leaving(event: &FocusEvent, node: &NodeRef) -> bool {
+    let related_node = event
+        .related_target()
+        .and_then(|target| target.dyn_into::<Node>().ok());
+
+    match (node.get(), related_node) {
+        (Some(node), Some(related_node)) => !node.contains(Some(&related_node)),
+        _ => true,
+    }
+}
+
 /// Focuses an element in the DOM.
 pub fn focus_element(node: &NodeRef) {
-    if let Some(node) = node.get() {
-        node.dyn_ref::<HtmlElement>().unwrap().focus().unwrap();
+    if let Some(element) = node.cast::<HtmlElement>() {
+        let _ = element.focus();
+    }
+}
+
+/// Focuses the element with the given ID.
+pub fn focus_element_by_id(id: &str) {
+    let element = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.get_element_by_id(id))
+        .and_then(|element| element.dyn_into::<HtmlElement>().ok());
+
+    if let Some(element) = element {
+        let _ = element.focus();
+    }
+}
+
+/// Determines whether the caret of an input element is at the start of its
+/// value, with no text selected.
+pub fn caret_at_start(node: &NodeRef) -> bool {
+    node.cast::<HtmlInputElement>().is_some_and(|input| {
+        input.selection_start().ok().flatten().unwrap_or(0) == 0
+            && input.selection_end().ok().flatten().unwrap_or(0) == 0
+    })
+}
+
+/// Scrolls an element in the DOM into view.
+pub fn scroll_to_element(node: &NodeRef) {
+    if let Some(element) = node.cast::<HtmlElement>() {
+        element.scroll_into_view();
+    }
+}
+
+/// Scrolls the element with the given ID just enough for it to be fully
+/// visible within its scrollable ancestors.
+pub fn scroll_into_view_nearest(id: &str) {
+    let element = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.get_element_by_id(id));
+
+    if let Some(element) = element {
+        let options = ScrollIntoViewOptions::new();
+        options.set_block(ScrollLogicalPosition::Nearest);
+        element.scroll_into_view_with_scroll_into_view_options(&options);
     }
 }
 
+/// Determines whether the target of a scroll event has been scrolled to
+/// within the given number of pixels of its bottom edge.
+pub fn scrolled_near_bottom(event: &Event, threshold: i32) -> bool {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<Element>().ok())
+        .is_some_and(|element| {
+            element.scroll_top() + element.client_height() >= element.scroll_height() - threshold
+        })
+}
+
 /// Selects the content of an element in the DOM.
 pub fn select_element_content(node: &NodeRef) {
     if let Some(node) = node.get() {
@@ -0,0 +1,138 @@
+/// Lowercases a string exactly as `str::to_lowercase` does, pairing each
+/// lowercase character with the index of the character it came from. Some
+/// characters lowercase to several characters, e.g. 'İ' to "i\u{307}", in
+/// which case the index is repeated.
+pub fn fold_case(value: &str) -> Vec<(usize, char)> {
+    let lowercase = value.to_lowercase();
+    let mut lowercase_chars = lowercase.chars();
+    let mut folded = Vec::with_capacity(lowercase.len());
+
+    // Each character lowercases to the same number of characters within the
+    // string as it does on its own, so the two can be lined up.
+    for (index, c) in value.chars().enumerate() {
+        for _ in 0..c.to_lowercase().count() {
+            if let Some(lowercase_char) = lowercase_chars.next() {
+                folded.push((index, lowercase_char));
+            }
+        }
+    }
+
+    folded
+}
+
+/// Compares an option to a typed out value, returning a score indicating the
+/// strength of the match along with the indices of the matched characters
+/// within the option, or `None` if the strings do not match. Lower scores
+/// indicate stronger matches. Both strings are lowercased as a whole before
+/// being compared, and the indices refer to the characters of the original
+/// option.
+pub fn option_match_indices(option: &str, value: &str) -> Option<(usize, Vec<usize>)> {
+    let option = fold_case(option);
+    let value = value.to_lowercase();
+
+    if option.iter().map(|(_, c)| *c).eq(value.chars()) {
+        let mut indices = option.iter().map(|(index, _)| *index).collect::<Vec<_>>();
+        indices.dedup();
+        return Some((0, indices));
+    }
+
+    let mut score = 0;
+    let mut indices_since_last_match = 0;
+    let mut indices = Vec::<usize>::new();
+    let mut value_chars = value.chars().peekable();
+
+    for (index, option_char) in option {
+        indices_since_last_match += 1;
+
+        match value_chars.peek() {
+            Some(value_char) => {
+                if option_char == *value_char {
+                    score += indices_since_last_match;
+                    indices_since_last_match = 0;
+                    value_chars.next();
+
+                    if indices.last() != Some(&index) {
+                        indices.push(index);
+                    }
+                }
+            }
+            None => break,
+        }
+    }
+
+    if !indices.is_empty() && value_chars.next().is_none() {
+        Some((score, indices))
+    } else {
+        None
+    }
+}
+
+/// Compares an option to a typed out value, returning a score indicating the
+/// strength of the match, or `None` if the strings do not match.
+pub fn option_match(option: &str, value: &str) -> Option<usize> {
+    option_match_indices(option, value).map(|(score, _)| score)
+}
+
+/// Limits the number of options.
+pub fn limit_options<T: Clone>(options: &[T], limit: usize) -> Vec<T> {
+    let limit_index = if options.len() > limit {
+        limit
+    } else {
+        options.len()
+    };
+
+    (options[..limit_index]).to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fold_case_keeps_original_indices() {
+        assert_eq!(fold_case("aB"), [(0, 'a'), (1, 'b')]);
+        assert_eq!(fold_case("İx"), [(0, 'i'), (0, '\u{307}'), (1, 'x')]);
+        assert_eq!(fold_case(""), []);
+    }
+
+    #[test]
+    fn fold_case_matches_string_lowercase() {
+        for value in ["ΟΔΟΣ ΟΔΟΣ", "İstanbul", "Straße", "ǅemal"] {
+            let folded = fold_case(value)
+                .into_iter()
+                .map(|(_, c)| c)
+                .collect::<String>();
+            assert_eq!(folded, value.to_lowercase(), "{value}");
+        }
+    }
+
+    #[test]
+    fn option_match_is_case_insensitive() {
+        assert_eq!(
+            option_match_indices("Apple", "apple"),
+            Some((0, vec![0, 1, 2, 3, 4]))
+        );
+        assert_eq!(
+            option_match_indices("Apple", "APL"),
+            Some((4, vec![0, 1, 3]))
+        );
+        assert_eq!(option_match_indices("Apple", "apz"), None);
+        assert_eq!(option_match_indices("Apple", ""), None);
+    }
+
+    #[test]
+    fn option_match_multi_char_lowercase() {
+        // 'İ' lowercases to 'i' followed by a combining dot, as in the
+        // lowercased string, so a plain 'i' matches it.
+        assert_eq!(option_match_indices("İzmir", "iz"), Some((3, vec![0, 1])));
+        assert_eq!(option_match_indices("İ", "i\u{307}"), Some((0, vec![0])));
+        assert_eq!(option_match_indices("İ", "İ"), Some((0, vec![0])));
+        assert_eq!(option_match_indices("Kİ", "ki"), Some((2, vec![0, 1])));
+    }
+
+    #[test]
+    fn option_match_final_sigma() {
+        assert_eq!(option_match("ΟΔΟΣ", "οδος"), Some(0));
+        assert_eq!(option_match("ΟΔΟΣ", "οδοσ"), None);
+    }
+}
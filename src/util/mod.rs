@@ -1,10 +1,16 @@
 mod console;
 mod dom;
 mod id;
+mod matching;
 mod number;
+mod state;
+mod storage;
 
 #[allow(unused_imports)]
 pub use console::*;
 pub use dom::*;
 pub use id::*;
+pub use matching::*;
 pub use number::*;
+pub use state::*;
+pub use storage::*;
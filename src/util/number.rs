@@ -24,6 +24,13 @@ pub trait Number:
     const DECIMAL: bool;
 
     fn as_f64(self) -> f64;
+
+    /// Adds two numbers, returning `None` if the result overflows.
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    /// Subtracts one number from another, returning `None` if the result
+    /// overflows.
+    fn checked_sub(self, other: Self) -> Option<Self>;
 }
 
 /// Implements the `Number` trait for integer primitives.
@@ -39,6 +46,14 @@ macro_rules! impl_number_int {
                 fn as_f64(self) -> f64 {
                     self as f64
                 }
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$ty>::checked_add(self, other)
+                }
+
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    <$ty>::checked_sub(self, other)
+                }
             }
         )*
     };
@@ -57,6 +72,14 @@ macro_rules! impl_number_float {
                 fn as_f64(self) -> f64 {
                     self as f64
                 }
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    Some(self + other)
+                }
+
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    Some(self - other)
+                }
             }
         )*
     };
@@ -0,0 +1,119 @@
+use std::ops::Deref;
+use yew::prelude::*;
+
+/// A handle to a component's value. The value is backed by a state handle,
+/// by a controlled value with a change callback, or by internal state if
+/// neither is provided.
+#[derive(Clone, PartialEq)]
+pub struct ValueHandle<T> {
+    /// The current value.
+    value: T,
+    /// The state to update when the value is set, if any.
+    state: Option<UseStateHandle<T>>,
+    /// The internal state to update when the value is set, for values with
+    /// no default.
+    internal: Option<UseStateHandle<Option<T>>>,
+    /// The callback called when the value is set.
+    on_change: Callback<T>,
+}
+
+impl<T: Clone> ValueHandle<T> {
+    /// Sets the value, updating the backing state and calling the change
+    /// callback.
+    pub fn set(&self, value: T) {
+        if let Some(state) = &self.state {
+            state.set(value.clone());
+        }
+
+        if let Some(internal) = &self.internal {
+            internal.set(Some(value.clone()));
+        }
+
+        self.on_change.emit(value);
+    }
+}
+
+impl<T> Deref for ValueHandle<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+/// Resolves a component's value from its `state`, `value` and `on_change`
+/// properties. A provided state handle takes precedence over a controlled
+/// value, and internal state is used if neither is provided.
+#[hook]
+pub fn use_value_handle<T>(
+    state: Option<UseStateHandle<T>>,
+    value: Option<T>,
+    on_change: Callback<T>,
+) -> ValueHandle<T>
+where
+    T: Clone + Default + 'static,
+{
+    let internal_state = use_state(|| value.clone().unwrap_or_default());
+
+    match (state, value) {
+        (Some(state), _) => ValueHandle {
+            value: (*state).clone(),
+            state: Some(state),
+            internal: None,
+            on_change,
+        },
+        (None, Some(value)) => ValueHandle {
+            value,
+            state: None,
+            internal: None,
+            on_change,
+        },
+        (None, None) => ValueHandle {
+            value: (*internal_state).clone(),
+            state: Some(internal_state),
+            internal: None,
+            on_change,
+        },
+    }
+}
+
+/// Resolves a component's value like `use_value_handle`, for values with no
+/// default. If neither a state handle nor a controlled value is provided, and
+/// no value has been set yet, `fallback` is used. Returns `None` if there is
+/// no value at all.
+#[hook]
+pub fn use_value_handle_or<T>(
+    state: Option<UseStateHandle<T>>,
+    value: Option<T>,
+    on_change: Callback<T>,
+    fallback: Option<T>,
+) -> Option<ValueHandle<T>>
+where
+    T: Clone + 'static,
+{
+    let internal_state = use_state(|| None::<T>);
+
+    match (state, value) {
+        (Some(state), _) => Some(ValueHandle {
+            value: (*state).clone(),
+            state: Some(state),
+            internal: None,
+            on_change,
+        }),
+        (None, Some(value)) => Some(ValueHandle {
+            value,
+            state: None,
+            internal: None,
+            on_change,
+        }),
+        (None, None) => (*internal_state)
+            .clone()
+            .or(fallback)
+            .map(|value| ValueHandle {
+                value,
+                state: None,
+                internal: Some(internal_state),
+                on_change,
+            }),
+    }
+}
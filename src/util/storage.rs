@@ -0,0 +1,27 @@
+use web_sys::Storage;
+
+/// Gets the window's local storage, if it is available.
+pub fn local_storage() -> Option<Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+/// Gets the window's session storage, if it is available.
+pub fn session_storage() -> Option<Storage> {
+    web_sys::window()?.session_storage().ok()?
+}
+
+/// Gets an item from storage.
+pub fn storage_get(storage: &Storage, key: &str) -> Option<String> {
+    storage.get_item(key).ok()?
+}
+
+/// Sets an item in storage. Failures, such as the storage quota being
+/// exceeded, are ignored.
+pub fn storage_set(storage: &Storage, key: &str, value: &str) {
+    let _ = storage.set_item(key, value);
+}
+
+/// Removes an item from storage.
+pub fn storage_remove(storage: &Storage, key: &str) {
+    let _ = storage.remove_item(key);
+}
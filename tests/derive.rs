@@ -0,0 +1,6 @@
+#[test]
+fn derive_yin_form() {
+    let cases = trybuild::TestCases::new();
+    cases.pass("tests/derive/pass/*.rs");
+    cases.compile_fail("tests/derive/fail/*.rs");
+}
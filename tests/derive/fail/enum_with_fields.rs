@@ -0,0 +1,9 @@
+use yin::YinForm;
+
+#[derive(Debug, Clone, PartialEq, YinForm)]
+enum Role {
+    Admin,
+    Custom(String),
+}
+
+fn main() {}
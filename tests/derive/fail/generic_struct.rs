@@ -0,0 +1,8 @@
+use yin::YinForm;
+
+#[derive(Debug, Clone, PartialEq, YinForm)]
+struct Profile<T> {
+    name: T,
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use yin::YinForm;
+
+#[derive(Debug, Clone, PartialEq, YinForm)]
+struct Profile(String);
+
+fn main() {}
@@ -0,0 +1,9 @@
+use yin::YinForm;
+
+#[derive(Debug, Clone, PartialEq, YinForm)]
+struct Profile {
+    #[yin(colour = "red")]
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use yin::YinForm;
+
+#[derive(Debug, Clone, PartialEq, YinForm)]
+struct Profile {
+    #[yin(required, min = 1)]
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use yin::YinForm;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Address {
+    street: String,
+}
+
+#[derive(Debug, Clone, PartialEq, YinForm)]
+struct Profile {
+    name: String,
+    address: Address,
+}
+
+fn main() {}
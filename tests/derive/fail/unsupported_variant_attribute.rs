@@ -0,0 +1,9 @@
+use yin::YinForm;
+
+#[derive(Debug, Clone, PartialEq, YinForm)]
+enum Role {
+    #[yin(required)]
+    Admin,
+}
+
+fn main() {}
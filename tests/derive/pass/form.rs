@@ -0,0 +1,50 @@
+use yin::chrono::NaiveDate;
+use yin::YinForm;
+
+#[derive(Debug, Clone, Copy, PartialEq, YinForm)]
+enum Role {
+    Admin,
+    #[yin(label = "Regular user")]
+    RegularUser,
+}
+
+#[derive(Debug, Clone, PartialEq, YinForm)]
+struct Profile {
+    #[yin(required, placeholder = "Your name")]
+    name: String,
+    #[yin(checkbox)]
+    subscribed: bool,
+    active: bool,
+    birthday: NaiveDate,
+    joined: Option<NaiveDate>,
+    #[yin(min = 0, max = 120)]
+    age: u8,
+    #[yin(decimals = 2)]
+    balance: f64,
+    #[yin(required, options = vec!["rust".to_owned()])]
+    tags: Vec<String>,
+    #[yin(select, help = "What the user can do")]
+    role: Role,
+}
+
+#[yew::function_component]
+fn App() -> yew::Html {
+    let state = yew::use_state(|| Profile {
+        name: String::new(),
+        subscribed: false,
+        active: true,
+        birthday: NaiveDate::from_ymd_opt(2000, 1, 1).unwrap(),
+        joined: None,
+        age: 30,
+        balance: 0.0,
+        tags: Vec::new(),
+        role: Role::Admin,
+    });
+    let on_submit = yew::Callback::from(|_: Profile| ());
+
+    yew::html! {
+        <ProfileForm {state} submit_label="Save" id="profile" {on_submit} />
+    }
+}
+
+fn main() {}
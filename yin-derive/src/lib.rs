@@ -0,0 +1,568 @@
+#![forbid(unsafe_code)]
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+use syn::{
+    parse_macro_input, Attribute, Data, DataEnum, DataStruct, DeriveInput, Error, Expr, Fields,
+    GenericArgument, Ident, LitStr, PathArguments, Result, Type,
+};
+
+/// Numeric primitive types, which are mapped to `NumberInput`.
+const NUMBER_TYPES: &[&str] = &[
+    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize", "f32",
+    "f64",
+];
+
+/// The kind of component a struct field is mapped to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FieldKind {
+    /// A `String`, mapped to `Input`.
+    Text,
+    /// A `bool`, mapped to `Switch` or `Checkbox`.
+    Bool,
+    /// A `NaiveDate`, mapped to `DatePicker`.
+    Date,
+    /// An `Option<NaiveDate>`, mapped to `DatePicker`.
+    OptionalDate,
+    /// A numeric primitive, mapped to `NumberInput`.
+    Number,
+    /// A `Vec<String>`, mapped to `Chips`.
+    List,
+    /// A type implementing `FormEnum`, marked with `#[yin(select)]`, mapped
+    /// to `Select`.
+    Enum,
+}
+
+impl FieldKind {
+    /// Describes the fields of this kind, for use in error messages.
+    fn description(&self) -> &'static str {
+        match *self {
+            Self::Text => "`String` fields",
+            Self::Bool => "`bool` fields",
+            Self::Date | Self::OptionalDate => "date fields",
+            Self::Number => "number fields",
+            Self::List => "`Vec<String>` fields",
+            Self::Enum => "select fields",
+        }
+    }
+
+    /// Gets the `#[yin(...)]` attributes that have an effect on fields of
+    /// this kind.
+    fn supported_attrs(&self) -> &'static [&'static str] {
+        match *self {
+            Self::Text => &["label", "placeholder", "help", "required"],
+            Self::Bool => &["label", "help", "checkbox"],
+            Self::Date | Self::OptionalDate => &["label", "help", "required", "min", "max"],
+            Self::Number => &[
+                "label",
+                "placeholder",
+                "help",
+                "required",
+                "min",
+                "max",
+                "decimals",
+            ],
+            Self::List => &["label", "placeholder", "help", "required", "options"],
+            Self::Enum => &["label", "help", "required", "select"],
+        }
+    }
+}
+
+/// Options set through `#[yin(...)]` attributes.
+#[derive(Default)]
+struct YinAttrs {
+    /// The field or variant label.
+    label: Option<String>,
+    /// The field placeholder text.
+    placeholder: Option<String>,
+    /// The field help text.
+    help: Option<String>,
+    /// The minimum value.
+    min: Option<Expr>,
+    /// The maximum value.
+    max: Option<Expr>,
+    /// The maximum number of decimal places.
+    decimals: Option<Expr>,
+    /// The list of chip options.
+    options: Option<Expr>,
+    /// Whether the field is required.
+    required: bool,
+    /// Whether a boolean field uses a checkbox rather than a switch.
+    checkbox: bool,
+    /// Whether the field is a `FormEnum` chosen with a select.
+    select: bool,
+    /// The name and location of each attribute that was set.
+    present: Vec<(String, Span)>,
+}
+
+/// Parses the `#[yin(...)]` attributes on an item.
+fn parse_attrs(attrs: &[Attribute]) -> Result<YinAttrs> {
+    let mut yin_attrs = YinAttrs::default();
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("yin")) {
+        attr.parse_nested_meta(|meta| {
+            if let Some(ident) = meta.path.get_ident() {
+                yin_attrs
+                    .present
+                    .push((ident.to_string(), meta.path.span()));
+            }
+
+            if meta.path.is_ident("label") {
+                yin_attrs.label = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("placeholder") {
+                yin_attrs.placeholder = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("help") {
+                yin_attrs.help = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("min") {
+                yin_attrs.min = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("max") {
+                yin_attrs.max = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("decimals") {
+                yin_attrs.decimals = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("options") {
+                yin_attrs.options = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("required") {
+                yin_attrs.required = true;
+            } else if meta.path.is_ident("checkbox") {
+                yin_attrs.checkbox = true;
+            } else if meta.path.is_ident("select") {
+                yin_attrs.select = true;
+            } else {
+                return Err(meta.error("unsupported yin attribute"));
+            }
+
+            Ok(())
+        })?;
+    }
+
+    Ok(yin_attrs)
+}
+
+/// Gets the last path segment of a type, along with its first generic type
+/// argument, if any.
+fn type_segment(ty: &Type) -> Option<(String, Option<&Type>)> {
+    match ty {
+        Type::Path(type_path) => type_path.path.segments.last().map(|segment| {
+            let argument = match &segment.arguments {
+                PathArguments::AngleBracketed(arguments) => {
+                    arguments.args.iter().find_map(|argument| match argument {
+                        GenericArgument::Type(ty) => Some(ty),
+                        _ => None,
+                    })
+                }
+                _ => None,
+            };
+
+            (segment.ident.to_string(), argument)
+        }),
+        _ => None,
+    }
+}
+
+/// Determines which kind of component a field type is mapped to. Types that
+/// are not recognized must be marked with `#[yin(select)]`, rather than being
+/// assumed to implement `FormEnum`.
+fn field_kind(ty: &Type, attrs: &YinAttrs) -> Result<FieldKind> {
+    if attrs.select {
+        return Ok(FieldKind::Enum);
+    }
+
+    let inner_name = |inner: Option<&Type>| inner.and_then(type_segment).map(|(name, _)| name);
+
+    match type_segment(ty) {
+        Some((name, _)) if name == "String" => Ok(FieldKind::Text),
+        Some((name, _)) if name == "bool" => Ok(FieldKind::Bool),
+        Some((name, _)) if name == "NaiveDate" => Ok(FieldKind::Date),
+        Some((name, inner))
+            if name == "Option" && inner_name(inner).as_deref() == Some("NaiveDate") =>
+        {
+            Ok(FieldKind::OptionalDate)
+        }
+        Some((name, inner)) if name == "Vec" && inner_name(inner).as_deref() == Some("String") => {
+            Ok(FieldKind::List)
+        }
+        Some((name, _)) if NUMBER_TYPES.contains(&name.as_str()) => Ok(FieldKind::Number),
+        _ => Err(Error::new_spanned(
+            ty,
+            "unsupported YinForm field type; mark the field with `#[yin(select)]` if the type implements `FormEnum`",
+        )),
+    }
+}
+
+/// Rejects any attributes that would have no effect on an item.
+fn check_attrs(attrs: &YinAttrs, supported: &[&str], description: &str) -> Result<()> {
+    match attrs
+        .present
+        .iter()
+        .find(|(name, _)| !supported.contains(&name.as_str()))
+    {
+        Some((name, span)) => Err(Error::new(
+            *span,
+            format!("`{name}` is not supported on {description}"),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Converts an identifier into a human readable label, e.g. `first_name` or
+/// `FirstName` into "First name".
+fn ident_label(ident: &Ident) -> String {
+    let ident = ident.to_string();
+    let mut words = Vec::<String>::new();
+
+    for c in ident.trim_start_matches("r#").chars() {
+        if c == '_' {
+            words.push(String::new());
+        } else if c.is_uppercase() || words.is_empty() {
+            words.push(c.to_lowercase().collect());
+        } else {
+            words.last_mut().unwrap().push(c);
+        }
+    }
+
+    let label = words
+        .into_iter()
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mut chars = label.chars();
+
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => label,
+    }
+}
+
+/// Generates the component markup for a single struct field.
+fn field_html(ident: &Ident, ty: &Type, attrs: &YinAttrs) -> Result<TokenStream2> {
+    let kind = field_kind(ty, attrs)?;
+    check_attrs(attrs, kind.supported_attrs(), kind.description())?;
+    let name = ident.to_string();
+    let label = attrs.label.clone().unwrap_or_else(|| ident_label(ident));
+    let placeholder = attrs.placeholder.clone().unwrap_or_default();
+    let help = attrs.help.clone().unwrap_or_default();
+    let required = attrs.required;
+    let min = attrs.min.as_ref().map(|min| quote! { min={#min} });
+    let max = attrs.max.as_ref().map(|max| quote! { max={#max} });
+    let decimals = attrs
+        .decimals
+        .as_ref()
+        .map(|decimals| quote! { decimals={#decimals} });
+    let options = attrs
+        .options
+        .as_ref()
+        .map(|options| quote! { #options })
+        .unwrap_or_else(|| quote! { ::std::vec::Vec::new() });
+
+    let update = |value_ty: TokenStream2, assign: TokenStream2| {
+        quote! {
+            let on_change = {
+                let form_state = form_state.clone();
+                ::yew::Callback::from(move |value: #value_ty| {
+                    form_state.update(move |new_state| {
+                        #assign
+                    });
+                })
+            };
+        }
+    };
+
+    Ok(match kind {
+        FieldKind::Text => {
+            let on_change = update(
+                quote! { ::std::string::String },
+                quote! { new_state.#ident = value; },
+            );
+            quote! {{
+                #on_change
+                ::yew::html! {
+                    <::yin::Input
+                        name={#name}
+                        label={#label}
+                        help={#help}
+                        placeholder={#placeholder}
+                        required={#required}
+                        value={(*state).#ident.clone()}
+                        {on_change}
+                    />
+                }
+            }}
+        }
+        FieldKind::Bool => {
+            let on_change = update(quote! { bool }, quote! { new_state.#ident = value; });
+            let component = if attrs.checkbox {
+                quote! { ::yin::Checkbox }
+            } else {
+                quote! { ::yin::Switch }
+            };
+            quote! {{
+                #on_change
+                ::yew::html! {
+                    <#component
+                        name={#name}
+                        label={#label}
+                        help={#help}
+                        value={(*state).#ident}
+                        {on_change}
+                    />
+                }
+            }}
+        }
+        FieldKind::Date | FieldKind::OptionalDate => {
+            let (value, assign) = if kind == FieldKind::Date {
+                (
+                    quote! { ::std::option::Option::Some((*state).#ident) },
+                    quote! {
+                        if let ::std::option::Option::Some(value) = value {
+                            new_state.#ident = value;
+                        }
+                    },
+                )
+            } else {
+                (
+                    quote! { (*state).#ident },
+                    quote! { new_state.#ident = value; },
+                )
+            };
+            let on_change = update(
+                quote! { ::std::option::Option<::yin::chrono::NaiveDate> },
+                assign,
+            );
+            quote! {{
+                #on_change
+                ::yew::html! {
+                    <::yin::DatePicker
+                        name={#name}
+                        label={#label}
+                        help={#help}
+                        required={#required}
+                        value={#value}
+                        {on_change}
+                        #min
+                        #max
+                    />
+                }
+            }}
+        }
+        FieldKind::Number => {
+            let on_change = update(quote! { #ty }, quote! { new_state.#ident = value; });
+            quote! {{
+                #on_change
+                ::yew::html! {
+                    <::yin::NumberInput<#ty>
+                        name={#name}
+                        label={#label}
+                        help={#help}
+                        placeholder={#placeholder}
+                        required={#required}
+                        value={(*state).#ident}
+                        {on_change}
+                        #min
+                        #max
+                        #decimals
+                    />
+                }
+            }}
+        }
+        FieldKind::List => {
+            let on_change = update(
+                quote! { ::std::vec::Vec<::std::string::String> },
+                quote! { new_state.#ident = value; },
+            );
+            quote! {{
+                #on_change
+                ::yew::html! {
+                    <::yin::Chips
+                        name={#name}
+                        label={#label}
+                        help={#help}
+                        placeholder={#placeholder}
+                        required={#required}
+                        value={(*state).#ident.clone()}
+                        {on_change}
+                        options={#options}
+                    />
+                }
+            }}
+        }
+        FieldKind::Enum => {
+            let on_change = update(quote! { #ty }, quote! { new_state.#ident = value; });
+            quote! {{
+                #on_change
+                ::yew::html! {
+                    <::yin::Select<#ty>
+                        name={#name}
+                        label={#label}
+                        help={#help}
+                        required={#required}
+                        value={::std::clone::Clone::clone(&(*state).#ident)}
+                        {on_change}
+                        options={<#ty as ::yin::FormEnum>::variants()}
+                    />
+                }
+            }}
+        }
+    })
+}
+
+/// Derives a form component for a struct with named fields.
+fn derive_struct(input: &DeriveInput, data: &DataStruct) -> Result<TokenStream2> {
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "YinForm cannot be derived for generic structs",
+        ));
+    }
+
+    let fields = match &data.fields {
+        Fields::Named(fields) => &fields.named,
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "YinForm can only be derived for structs with named fields",
+            ))
+        }
+    };
+
+    let vis = &input.vis;
+    let ident = &input.ident;
+    let component_ident = format_ident!("{}Form", ident);
+    let props_ident = format_ident!("{}FormProps", ident);
+    let component_doc = format!("A form component generated for `{ident}`.");
+    let props_doc = format!("`{component_ident}` properties.");
+    let fields_html = fields
+        .iter()
+        .map(|field| {
+            let attrs = parse_attrs(&field.attrs)?;
+            field_html(field.ident.as_ref().unwrap(), &field.ty, &attrs)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        #[doc = #props_doc]
+        #[derive(::yew::Properties, ::std::cmp::PartialEq, ::std::clone::Clone)]
+        #vis struct #props_ident {
+            /// The form state.
+            pub state: ::yew::UseStateHandle<#ident>,
+            /// The submit button label. Will not be created if empty.
+            #[prop_or_default]
+            pub submit_label: ::std::string::String,
+            /// The form ID, used as the key for the form's persisted draft.
+            /// Drafts are not persisted if empty.
+            #[prop_or_default]
+            pub id: ::std::string::String,
+            /// Where in-progress field values are persisted.
+            #[prop_or_default]
+            pub draft_storage: ::yin::DraftStorage,
+            /// The callback called with the form state when the form is
+            /// submitted and every field is valid.
+            #[prop_or(::yew::Callback::from(|_| ()))]
+            pub on_submit: ::yew::Callback<#ident>,
+        }
+
+        #[doc = #component_doc]
+        #[::yew::function_component]
+        #vis fn #component_ident(props: &#props_ident) -> ::yew::Html {
+            let state = props.state.clone();
+            let form_state = ::yin::use_derived_form_state(&state);
+            let submit_label = props.submit_label.clone();
+            let id = props.id.clone();
+            let draft_storage = props.draft_storage;
+            let on_submit = {
+                let state = state.clone();
+                let on_submit = props.on_submit.clone();
+                ::yew::Callback::from(move |_: ::yin::FormValues| {
+                    on_submit.emit(::std::clone::Clone::clone(&*state));
+                })
+            };
+
+            ::yew::html! {
+                <::yin::Form {on_submit} {submit_label} {id} {draft_storage}>
+                    #({#fields_html})*
+                </::yin::Form>
+            }
+        }
+    })
+}
+
+/// Derives an implementation of `FormEnum` for a fieldless enum.
+fn derive_enum(input: &DeriveInput, data: &DataEnum) -> Result<TokenStream2> {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mut variants = Vec::new();
+    let mut labels = Vec::new();
+
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new_spanned(
+                variant,
+                "YinForm can only be derived for enums without fields",
+            ));
+        }
+
+        let attrs = parse_attrs(&variant.attrs)?;
+        check_attrs(&attrs, &["label"], "enum variants")?;
+        let variant_ident = &variant.ident;
+        let label = attrs.label.unwrap_or_else(|| ident_label(variant_ident));
+        variants.push(quote! { Self::#variant_ident });
+        labels.push(quote! { Self::#variant_ident => ::std::borrow::ToOwned::to_owned(#label) });
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::yin::FormEnum for #ident #ty_generics #where_clause {
+            fn variants() -> ::std::vec::Vec<Self> {
+                ::std::vec![#(#variants),*]
+            }
+
+            fn label(&self) -> ::std::string::String {
+                match *self {
+                    #(#labels,)*
+                }
+            }
+        }
+    })
+}
+
+/// Derives a form from a struct, or makes a fieldless enum selectable within
+/// such a form.
+///
+/// On a struct with named fields, this generates a `{Name}Form` component
+/// and its `{Name}FormProps` properties. Each field is mapped to a component
+/// by its type:
+///
+/// - `String` is mapped to `Input`
+/// - `bool` is mapped to `Switch`, or `Checkbox` with `#[yin(checkbox)]`
+/// - `NaiveDate` and `Option<NaiveDate>` are mapped to `DatePicker`
+/// - numeric primitives are mapped to `NumberInput`
+/// - `Vec<String>` is mapped to `Chips`
+/// - types implementing `FormEnum` are mapped to `Select`, and must be marked
+///   with `#[yin(select)]`
+///
+/// Any other field type is a compile error. Fields can be configured with
+/// `#[yin(label = "...", placeholder = "...", help = "...", min = ...,
+/// max = ..., decimals = ..., options = ..., required)]`. Setting an
+/// attribute that has no effect on the field's component, such as `min` on a
+/// `String`, is a compile error.
+///
+/// The generated code refers to the `yin` crate by name, along with its
+/// re-export of `chrono`.
+///
+/// On a fieldless enum, this implements `FormEnum`. Variant labels can be
+/// set with `#[yin(label = "...")]`.
+#[proc_macro_derive(YinForm, attributes(yin))]
+pub fn derive_yin_form(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let result = match &input.data {
+        Data::Struct(data) => derive_struct(&input, data),
+        Data::Enum(data) => derive_enum(&input, data),
+        Data::Union(_) => Err(Error::new_spanned(
+            &input.ident,
+            "YinForm cannot be derived for unions",
+        )),
+    };
+
+    result.unwrap_or_else(Error::into_compile_error).into()
+}